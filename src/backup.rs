@@ -0,0 +1,162 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use walkdir::WalkDir;
+
+pub const BACKUPS: &str = ".backups";
+pub const DEFAULT_ALLOWLIST: &[&str] = &["scripts/*.txt"];
+
+pub fn default_allowlist() -> Vec<String> {
+    DEFAULT_ALLOWLIST.iter().map(|p| p.to_string()).collect()
+}
+
+pub fn backup_files(hud: &Path, backups_dir: &Path, allowlist: &[String]) -> Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let backup_dir = backups_dir.join(timestamp.to_string());
+
+    for entry in WalkDir::new(hud).into_iter().flatten() {
+        let Ok(relative) = entry.path().strip_prefix(hud) else {
+            continue;
+        };
+
+        let relative_str = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if !entry.file_type().is_file() || !allowlist.iter().any(|p| glob(p, &relative_str)) {
+            continue;
+        }
+
+        let to = backup_dir.join(relative);
+        fs::create_dir_all(to.parent().unwrap())?;
+        fs::copy(entry.path(), &to)
+            .with_context(|| format!("failed to back up `{}`", entry.path().display()))?;
+    }
+
+    Ok(backup_dir)
+}
+
+// the whole hud as `<name>-<millis>.zip`, with its folder at the root like the zips
+// `install_from_zip` takes, so it can be installed again as is
+pub fn zip_backup(hud: &Path, name: &str, backups_dir: &Path) -> Result<PathBuf> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let zip = (millis..)
+        .map(|t| backups_dir.join(format!("{name}-{t}.zip")))
+        .find(|p| !p.exists())
+        .unwrap();
+
+    fs::create_dir_all(backups_dir)
+        .with_context(|| format!("failed to create `{}`", backups_dir.display()))?;
+    // a half written zip is worse than none, it looks like a backup
+    if let Err(e) = write_zip(hud, name, &zip) {
+        let _ = fs::remove_file(&zip);
+        return Err(e.context(format!("failed to write `{}`", zip.display())));
+    }
+
+    Ok(zip)
+}
+
+fn write_zip(hud: &Path, name: &str, to: &Path) -> Result<()> {
+    let mut zip = zip::ZipWriter::new(fs::File::create(to)?);
+    let options = zip::write::SimpleFileOptions::default();
+
+    // the history is in the remote already and would make every backup huge
+    let entries = WalkDir::new(hud)
+        .into_iter()
+        .filter_entry(|e| e.depth() != 1 || e.file_name() != ".git");
+    for entry in entries {
+        let entry = entry?;
+        let Ok(relative) = entry.path().strip_prefix(hud) else {
+            continue;
+        };
+
+        let path = std::iter::once(name.into())
+            .chain(
+                relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy()),
+            )
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if entry.file_type().is_dir() {
+            zip.add_directory(path, options)?;
+        } else if entry.file_type().is_file() {
+            zip.start_file(path, options)?;
+            io::copy(&mut fs::File::open(entry.path())?, &mut zip)?;
+        }
+    }
+    zip.finish()?;
+
+    Ok(())
+}
+
+// keeps the newest `keep` zips in `backups_dir`, returns how many went
+pub fn prune_zips(backups_dir: &Path, keep: usize) -> Result<usize> {
+    let Ok(read_dir) = fs::read_dir(backups_dir) else {
+        return Ok(0);
+    };
+
+    let mut zips = read_dir
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "zip"))
+        .collect::<Vec<_>>();
+    // the timestamps all have the same number of digits, so this goes oldest first
+    zips.sort_unstable();
+
+    let excess = zips.len().saturating_sub(keep);
+    for zip in &zips[..excess] {
+        fs::remove_file(zip).with_context(|| format!("failed to remove `{}`", zip.display()))?;
+    }
+
+    Ok(excess)
+}
+
+pub fn list_backups(backups_dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(backups_dir) else {
+        return Vec::new();
+    };
+
+    let mut backups = read_dir
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect::<Vec<_>>();
+    backups.sort_unstable();
+
+    backups
+}
+
+fn glob(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+
+    pattern.len() == path.len()
+        && pattern
+            .iter()
+            .zip(path)
+            .all(|(p, c)| wildcard(p.as_bytes(), c.as_bytes()))
+}
+
+fn wildcard(pattern: &[u8], s: &[u8]) -> bool {
+    match (pattern.first(), s.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            wildcard(&pattern[1..], s) || (!s.is_empty() && wildcard(pattern, &s[1..]))
+        }
+        (Some(p), Some(c)) if p.eq_ignore_ascii_case(c) => wildcard(&pattern[1..], &s[1..]),
+        _ => false,
+    }
+}
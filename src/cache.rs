@@ -0,0 +1,72 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::{Config, Hud, Mtimes};
+
+const SCAN_CACHE_JSON: &str = "scan_cache.json";
+
+// keyed by `custom` folder so every profile keeps its own entries
+type Cache = BTreeMap<PathBuf, Vec<Entry>>;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct Entry {
+    vdf: PathBuf,
+    mtimes: Mtimes,
+    hud: Hud,
+}
+
+fn path() -> Result<PathBuf> {
+    Config::dir()
+        .map(|d| d.join(SCAN_CACHE_JSON))
+        .with_context(|| "failed to find the config folder")
+}
+
+fn read(path: &Path) -> Result<Cache> {
+    if !path.exists() {
+        return Ok(Cache::new());
+    }
+
+    let cache =
+        fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+
+    serde_json::from_str(&cache).with_context(|| format!("`{}` is invalid", path.display()))
+}
+
+pub fn load(custom_dir: &Path) -> Result<HashMap<PathBuf, (Mtimes, Hud)>> {
+    let mut cache = read(&path()?)?;
+
+    Ok(cache
+        .remove(custom_dir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| (e.vdf, (e.mtimes, e.hud)))
+        .collect())
+}
+
+pub fn save(custom_dir: &Path, huds: &HashMap<PathBuf, (Mtimes, Hud)>) -> Result<()> {
+    let path = path()?;
+    // a broken cache is just rebuilt
+    let mut cache = read(&path).unwrap_or_default();
+
+    let entries = huds
+        .iter()
+        .filter(|(_, (mtimes, _))| mtimes.is_some())
+        .map(|(vdf, (mtimes, hud))| Entry {
+            vdf: vdf.clone(),
+            mtimes: *mtimes,
+            hud: hud.clone(),
+        })
+        .collect();
+    cache.insert(custom_dir.to_path_buf(), entries);
+    cache.retain(|dir, _| dir.is_dir());
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create `{}`", dir.display()))?;
+    }
+
+    let cache = serde_json::to_string(&cache)?;
+    fs::write(&path, cache).with_context(|| format!("failed to write `{}`", path.display()))
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::ActivationStrategy;
+
+const CONFIG_TOML: &str = "hud_manager.toml";
+const CONFIG_DIR_VAR: &str = "HUD_MANAGER_CONFIG_DIR";
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Config {
+    // used instead of looking for `custom` around the exe
+    pub custom_dir: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<Profile>,
+    pub toggle_pair: Option<(String, String)>,
+    // extra search terms for each hud, keyed by folder name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, Vec<String>>,
+    // more folders of huds besides `custom/huds`, huds in them are copied or linked into `custom`
+    // but never moved out
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub libraries: Vec<PathBuf>,
+    // how many zips of each hud to keep in `huds/.backups`, one is made whenever it's switched away
+    // from
+    pub backup_zips: Option<usize>,
+    // copies of the active hud's files matching `backup_allowlist`, `scripts/*.txt` if it's not
+    // set, are kept in `huds/.backups` from before each switch away from it
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub backup_before_switch: bool,
+    pub backup_allowlist: Option<Vec<String>>,
+    // how huds get into `custom` when switching, moving them out of `huds` if it's not set
+    pub activation_strategy: Option<ActivationStrategy>,
+}
+
+#[derive(Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Profile {
+    pub name: String,
+    pub custom_dir: PathBuf,
+}
+
+impl Config {
+    // everything the manager keeps outside `custom`, the config, cache and log, goes in here
+    pub fn dir() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os(CONFIG_DIR_VAR).filter(|d| !d.is_empty()) {
+            return Some(PathBuf::from(dir));
+        }
+
+        directories::ProjectDirs::from("", "", "hud_manager").map(|d| d.config_dir().to_path_buf())
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        Self::dir().map(|d| d.join(CONFIG_TOML))
+    }
+
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path().filter(|p| p.exists()) else {
+            return Ok(Self::default());
+        };
+
+        let config = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+        toml::from_str(&config).with_context(|| format!("`{}` is invalid", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path().with_context(|| "failed to find the config folder")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create `{}`", dir.display()))?;
+        }
+
+        let config = toml::to_string_pretty(self)?;
+        fs::write(&path, config).with_context(|| format!("failed to write `{}`", path.display()))
+    }
+}
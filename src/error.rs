@@ -0,0 +1,42 @@
+// what the public api fails with, so callers can tell a hud that's in the way from a failed move
+#[derive(Debug, thiserror::Error)]
+pub enum HudError {
+    #[error("{0}")]
+    CustomDirNotFound(String),
+    #[error("hud `{0}` no longer exists, rescan needed")]
+    HudNotFound(String),
+    #[error("`{hud}` is active, switch to another hud before {action} it")]
+    HudAlreadyActive { hud: String, action: &'static str },
+    #[error("`{0}` is disabled, enable it first")]
+    HudDisabled(String),
+    #[error("there are multiple huds called `{0}`, rename one of them")]
+    AmbiguousName(String),
+    #[error("a hud called `{0}` already exists")]
+    NameCollision(String),
+    #[error("`{0}` isn't a valid folder name")]
+    InvalidName(String),
+    #[error("a hud can't be called `{0}`, that's the folder huds are kept in, rename it")]
+    ReservedName(String),
+    // returned when installing with `ExistingHud::Refuse`, so the caller can ask what to do
+    // instead
+    #[error("hud `{0}` is already installed")]
+    AlreadyInstalled(String),
+    #[error("`{0}` has local changes, commit or stash them before updating")]
+    GitLocalChanges(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+// the internals use anyhow, a `HudError` that comes back through them without any context added
+// keeps its kind
+impl From<anyhow::Error> for HudError {
+    fn from(e: anyhow::Error) -> Self {
+        if !e.chain().next().is_some_and(|e| e.is::<Self>()) {
+            return Self::Other(e);
+        }
+
+        e.downcast().unwrap_or_else(Self::Other)
+    }
+}
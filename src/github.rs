@@ -0,0 +1,48 @@
+use anyhow::{bail, Context, Result};
+
+const MAX_DOWNLOAD: u64 = 500 * 1024 * 1024;
+
+// returns the repo name and its default branch (or the branch in a `/tree/<branch>` url) as a zip
+pub fn download_zip(url: &str) -> Result<(String, Vec<u8>)> {
+    let (repo, zip_url) = zip_url(url)?;
+
+    let zip = ureq::get(&zip_url)
+        .call()
+        .with_context(|| format!("failed to download `{zip_url}`"))?
+        .body_mut()
+        .with_config()
+        .limit(MAX_DOWNLOAD)
+        .read_to_vec()
+        .with_context(|| format!("failed to download `{zip_url}`"))?;
+
+    Ok((repo, zip))
+}
+
+fn zip_url(url: &str) -> Result<(String, String)> {
+    let path = url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .strip_prefix("github.com/")
+        .with_context(|| format!("`{url}` isn't a github url"))?;
+
+    let mut parts = path.trim_end_matches('/').split('/');
+    let (Some(owner), Some(repo)) = (parts.next(), parts.next()) else {
+        bail!("`{url}` isn't a link to a github repo");
+    };
+    let repo = repo.trim_end_matches(".git");
+    if owner.is_empty() || repo.is_empty() {
+        bail!("`{url}` isn't a link to a github repo");
+    }
+
+    let branch = match parts.next() {
+        Some("tree") => format!("refs/heads/{}", parts.collect::<Vec<_>>().join("/")),
+        _ => "HEAD".to_string(),
+    };
+
+    Ok((
+        repo.to_string(),
+        format!("https://github.com/{owner}/{repo}/archive/{branch}.zip"),
+    ))
+}
@@ -1,146 +1,2379 @@
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{Read, Seek};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{bail, ensure, Context, Result};
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Matcher, Utf32Str};
+use rayon::prelude::*;
 use walkdir::WalkDir;
 
+mod backup;
+mod cache;
+mod config;
+mod error;
+mod github;
+mod log;
+mod steam;
+mod vdf;
+mod watch;
+
+pub use config::{Config, Profile};
+pub use error::HudError;
+pub use log::init_logging;
+pub use watch::Watcher;
+
 const HUDS: &str = "huds";
 const INFO_VDF: &str = "info.vdf";
+const FAVORITES_JSON: &str = "favorites.json";
+// what favorites used to be kept in, one per line, moved over to `favorites.json` when found
 const FAVORITES_TXT: &str = "favorites.txt";
+const FAVORITES_VERSION: u32 = 1;
+const TAGS_JSON: &str = "tags.json";
+const NOTES_JSON: &str = "notes.json";
+// when each hud was last activated, kept with the huds instead of in the config since it belongs
+// to one `custom` folder rather than every profile, and it changes on every switch, which would
+// keep rewriting a file people edit by hand
+const ACTIVATED_JSON: &str = "activated.json";
+// which extra library the active hud was copied or linked from, so it goes back there
+const SOURCES_JSON: &str = "sources.json";
+// active huds that are copies of the one in `huds`, which stays there, so switching away removes
+// them rather than moving them back
+const COPIES_JSON: &str = "copies.json";
+const INSTALL_TMP: &str = ".installing";
+const REPLACED_TMP: &str = ".replaced";
+// tf2 never sees anything in `huds`, this just keeps the folders out of the way
+const DISABLED: &str = ".disabled";
+const BRANCH_SUFFIXES: &[&str] = &["-main", "-master"];
+const CUSTOM_DIR_VAR: &str = "HUD_MANAGER_CUSTOM_DIR";
+const PREVIEW_NAMES: &[&str] = &["screenshot", "preview", "thumbnail"];
+const PREVIEW_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+const PREVIEW_DIRS: &[&str] = &["", "materials", "resource"];
+const SCAN_PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SortMode {
+    #[default]
+    Name,
+    ModifiedNewest,
+    ModifiedOldest,
+}
+
+impl SortMode {
+    pub const ALL: [Self; 3] = [Self::Name, Self::ModifiedNewest, Self::ModifiedOldest];
+}
+
+impl std::fmt::Display for SortMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Name => "name",
+            Self::ModifiedNewest => "newest",
+            Self::ModifiedOldest => "oldest",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ActivationStrategy {
+    #[default]
+    Move,
+    // the library keeps its copy and `custom` gets a fresh one
+    Copy,
+    // the hud stays in the library and `custom` gets a link to it, a junction on windows if
+    // symlinks aren't allowed
+    Symlink,
+}
+
+impl ActivationStrategy {
+    pub const ALL: [Self; 3] = [Self::Move, Self::Copy, Self::Symlink];
+}
+
+impl std::fmt::Display for ActivationStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Move => "move",
+            Self::Copy => "copy",
+            Self::Symlink => "link",
+        })
+    }
+}
+
+// what to do when an install would land on a hud that's already there
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExistingHud {
+    #[default]
+    Refuse,
+    Overwrite,
+    // installs under the first free `name-2`, `name-3`...
+    KeepBoth,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Huds {
+    pub huds: Vec<Hud>,
+    pub active_hud: Option<Hud>,
+    #[serde(skip)]
+    pub profile: Option<Profile>,
+    pub sort_mode: SortMode,
+    pub backup_before_switch: bool,
+    pub backup_allowlist: Vec<String>,
+    // zips of a hud kept from before it was switched away from, 0 for none, from the config
+    #[serde(skip)]
+    pub backup_zips: usize,
+    #[serde(skip)]
+    pub dry_run: bool,
+    // the least time between two scan progress reports, so a big library doesn't flood
+    #[serde(skip)]
+    pub scan_progress_interval: Duration,
+    pub activation_strategy: ActivationStrategy,
+    favorites: Vec<String>,
+    tags: BTreeMap<String, Vec<String>>,
+    notes: BTreeMap<String, String>,
+    #[serde(skip)]
+    aliases: BTreeMap<String, Vec<String>>,
+    // scanned as well as `huds`, from the config
+    #[serde(skip)]
+    pub libraries: Vec<PathBuf>,
+    #[serde(skip)]
+    sources: BTreeMap<String, PathBuf>,
+    #[serde(skip)]
+    copies: BTreeSet<String>,
+    #[serde(skip)]
+    last_active: Option<String>,
+    // only means anything on this machine, so it's left out of exports
+    #[serde(skip)]
+    activated: BTreeMap<String, SystemTime>,
+    #[serde(skip)]
+    scan_cache: HashMap<PathBuf, (Mtimes, Hud)>,
+}
+
+type Mtimes = Option<(SystemTime, SystemTime)>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PullOutcome {
+    UpToDate,
+    FastForwarded { from: String, to: String },
+}
+
+impl std::fmt::Display for PullOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UpToDate => f.write_str("already up to date"),
+            Self::FastForwarded { from, to } => write!(f, "updated from {from} to {to}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScanProgress {
+    pub visited: usize,
+    pub current: String,
+}
+
+// `favorites.json`, and what favorites are exported as. unknown fields are ignored, so files from
+// newer versions still load
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+struct FavoritesFile {
+    version: u32,
+    favorites: Vec<FavEntry>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FavEntry {
+    name: String,
+}
+
+impl FavoritesFile {
+    fn new(names: impl Iterator<Item = String>) -> Self {
+        Self {
+            version: FAVORITES_VERSION,
+            favorites: names.map(|name| FavEntry { name }).collect(),
+        }
+    }
+
+    fn names(self) -> impl Iterator<Item = String> {
+        self.favorites.into_iter().map(|f| f.name)
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Favorites {
+    File(FavoritesFile),
+    // what older versions exported
+    Unversioned { favorites: Vec<String> },
+    List(Vec<String>),
+}
+
+impl Default for Huds {
+    fn default() -> Self {
+        Self {
+            huds: Vec::new(),
+            active_hud: None,
+            profile: None,
+            sort_mode: SortMode::default(),
+            backup_before_switch: false,
+            backup_allowlist: backup::default_allowlist(),
+            backup_zips: 0,
+            dry_run: false,
+            scan_progress_interval: SCAN_PROGRESS_INTERVAL,
+            activation_strategy: ActivationStrategy::default(),
+            favorites: Vec::new(),
+            tags: BTreeMap::new(),
+            notes: BTreeMap::new(),
+            aliases: BTreeMap::new(),
+            libraries: Vec::new(),
+            sources: BTreeMap::new(),
+            copies: BTreeSet::new(),
+            last_active: None,
+            activated: BTreeMap::new(),
+            scan_cache: HashMap::new(),
+        }
+    }
+}
+
+impl Huds {
+    fn custom_dir(&self) -> Result<PathBuf, HudError> {
+        let Some(profile) = &self.profile else {
+            return custom_dir_with_fallback();
+        };
+
+        if !profile.custom_dir.is_dir() {
+            return Err(HudError::CustomDirNotFound(format!(
+                "`{}` from profile `{}` isn't a folder",
+                profile.custom_dir.display(),
+                profile.name
+            )));
+        }
+
+        Ok(profile.custom_dir.clone())
+    }
+
+    // saved to the config so it's used from then on, wherever the exe is
+    pub fn set_custom_dir(&mut self, custom_dir: PathBuf) -> Result<(), HudError> {
+        if !custom_dir.is_dir() {
+            return Err(HudError::CustomDirNotFound(format!(
+                "`{}` isn't a folder",
+                custom_dir.display()
+            )));
+        }
+        if !looks_like_custom_dir(&custom_dir) {
+            return Err(HudError::CustomDirNotFound(format!(
+                "`{}` doesn't look like tf2's `custom` folder, it should be the one in `tf`",
+                custom_dir.display()
+            )));
+        }
+
+        let mut config = Config::load()?;
+        config.custom_dir = Some(custom_dir);
+        config.save()?;
+
+        // the configured folder is the default one, a profile would keep hiding it
+        self.profile = None;
+
+        Ok(())
+    }
+
+    pub fn set_activation_strategy(
+        &mut self,
+        activation_strategy: ActivationStrategy,
+    ) -> Result<(), HudError> {
+        let mut config = Config::load()?;
+        config.activation_strategy = Some(activation_strategy);
+        config.save()?;
+
+        self.activation_strategy = activation_strategy;
+
+        Ok(())
+    }
+
+    pub fn set_backup_before_switch(&mut self, backup_before_switch: bool) -> Result<(), HudError> {
+        let mut config = Config::load()?;
+        config.backup_before_switch = backup_before_switch;
+        config.save()?;
+
+        self.backup_before_switch = backup_before_switch;
+
+        Ok(())
+    }
+
+    pub fn scan_for_huds(&mut self) -> Result<(), HudError> {
+        self.scan_for_huds_with_progress(|_| {})
+    }
+
+    pub fn scan_for_huds_with_progress(
+        &mut self,
+        mut progress: impl FnMut(ScanProgress),
+    ) -> Result<(), HudError> {
+        Ok(self.scan(false, &mut progress)?)
+    }
+
+    pub fn scan_incremental(&mut self) -> Result<(), HudError> {
+        Ok(self.scan(true, &mut |_| {})?)
+    }
+
+    // like `scan_incremental`, but the first scan also reuses what was parsed last launch, a
+    // broken or missing cache only makes it slower
+    pub fn scan_for_huds_cached(&mut self) -> Result<(), HudError> {
+        self.scan_for_huds_cached_with_progress(|_| {})
+    }
+
+    pub fn scan_for_huds_cached_with_progress(
+        &mut self,
+        mut progress: impl FnMut(ScanProgress),
+    ) -> Result<(), HudError> {
+        let custom_dir = self.custom_dir()?;
+
+        if self.scan_cache.is_empty() {
+            match cache::load(&custom_dir) {
+                Ok(cache) => self.scan_cache = cache,
+                Err(e) => tracing::warn!("{e:#}"),
+            }
+        }
+        self.scan(true, &mut progress)?;
+
+        if let Err(e) = cache::save(&custom_dir, &self.scan_cache) {
+            tracing::warn!("{e:#}");
+        }
+
+        Ok(())
+    }
+
+    fn scan(&mut self, incremental: bool, progress: &mut dyn FnMut(ScanProgress)) -> Result<()> {
+        let custom_dir = self.custom_dir()?;
+        let _span = tracing::info_span!("scan", custom_dir = %custom_dir.display()).entered();
+
+        // once per hud folder at most, and no more often than `scan_progress_interval`
+        let interval = self.scan_progress_interval;
+        let mut visited = 0;
+        let mut last_report: Option<Instant> = None;
+        let mut report = |entry: &walkdir::DirEntry| {
+            let name = entry.file_name();
+            if entry.depth() != 1 || !entry.file_type().is_dir() || name == HUDS || name == DISABLED
+            {
+                return;
+            }
+
+            visited += 1;
+            if last_report.is_some_and(|t| t.elapsed() < interval) {
+                return;
+            }
+            last_report = Some(Instant::now());
+            progress(ScanProgress {
+                visited,
+                current: name.to_string_lossy().to_string(),
+            });
+        };
+
+        self.huds.clear();
+        self.active_hud = None;
+
+        let cache = std::mem::take(&mut self.scan_cache);
+        let walk_dir = |d: &Path| {
+            WalkDir::new(d)
+                .max_depth(2)
+                .follow_links(true)
+                .into_iter()
+                .flatten()
+        };
+
+        // only `hud/info.vdf` counts, so neither `custom` nor `huds` is ever taken for a hud
+        let huds_dir = custom_dir.join(HUDS);
+        if huds_dir.join(INFO_VDF).is_file() {
+            tracing::warn!(
+                "`{}` has an `{INFO_VDF}`, it's where huds are kept rather than a hud",
+                huds_dir.display()
+            );
+        }
+        let active_vdf = walk_dir(&custom_dir)
+            .inspect(&mut report)
+            .find(|e| e.depth() == 2 && is_info_vdf(e) && !e.path().starts_with(&huds_dir))
+            .map(walkdir::DirEntry::into_path);
+        let mut vdfs = |d: &Path| {
+            walk_dir(d)
+                .inspect(&mut report)
+                .filter(|e| e.depth() == 2 && is_info_vdf(e))
+                .map(walkdir::DirEntry::into_path)
+                .collect::<Vec<_>>()
+        };
+        let library_vdfs = vdfs(&custom_dir.join(HUDS));
+        let disabled_vdfs = vdfs(&custom_dir.join(HUDS).join(DISABLED));
+        let external_vdfs = self
+            .libraries
+            .iter()
+            .filter(|d| d.is_dir() && **d != custom_dir && **d != custom_dir.join(HUDS))
+            .map(|d| (d.clone(), vdfs(d)))
+            .collect::<Vec<_>>();
+
+        // reading and parsing each `info.vdf` is the slow part and every hud is independent, so
+        // that much happens in parallel and the rest goes in walk order like before
+        let parse = |vdf: PathBuf| {
+            let mtimes = mtimes(&vdf);
+            let hud = match cache.get(&vdf) {
+                Some((cached, hud)) if incremental && cached.is_some() && *cached == mtimes => {
+                    hud.clone()
+                }
+                _ => Hud::from_vdf(vdf.clone()),
+            };
+            (vdf, mtimes, hud)
+        };
+        let active = active_vdf.map(parse);
+        let library = library_vdfs.into_par_iter().map(parse).collect::<Vec<_>>();
+        let disabled = disabled_vdfs.into_par_iter().map(parse).collect::<Vec<_>>();
+        let external = external_vdfs
+            .into_iter()
+            .map(|(dir, vdfs)| (dir, vdfs.into_par_iter().map(parse).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        let mut finish = |(vdf, mtimes, mut hud): (PathBuf, Mtimes, Hud)| {
+            hud.modified = mtimes.map(|(dir, _)| dir);
+            self.scan_cache.insert(vdf, (mtimes, hud.clone()));
+
+            let key = favorite_key(&hud.name);
+            hud.favorite_rank = self.favorites.iter().position(|f| *f == key);
+            hud.favorite = hud.favorite_rank.is_some();
+            hud.tags = self.tags.get(&hud.name).cloned().unwrap_or_default();
+            hud.last_activated = self.activated.get(&hud.name).copied();
+            hud.note = self.notes.get(&hud.name).cloned();
+            hud.is_git_repo = hud.path.join(".git").exists();
+            hud
+        };
+
+        let active = active.map(&mut finish).map(|mut hud| {
+            hud.source = self.sources.get(&hud.name).cloned();
+            hud
+        });
+        let library = library.into_iter().map(&mut finish).collect::<Vec<_>>();
+        let disabled = disabled.into_iter().map(&mut finish).collect::<Vec<_>>();
+        let mut external = external
+            .into_iter()
+            .flat_map(|(dir, huds)| huds.into_iter().map(move |hud| (dir.clone(), hud)))
+            .map(|(dir, hud)| Hud {
+                source: Some(dir),
+                ..finish(hud)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(hud) = active {
+            self.huds.push(hud.clone());
+            self.active_hud = Some(hud);
+        }
+
+        let active_name = self.active_hud.as_ref().map(|h| h.name.clone());
+        let linked = self
+            .active_hud
+            .as_ref()
+            .filter(|h| is_link(&h.path))
+            .and_then(|h| fs::canonicalize(&h.path).ok());
+        for hud in library {
+            // when copying, the library copy and the active one are the same hud, list it once
+            if active_name
+                .as_ref()
+                .is_some_and(|a| *a == hud.name && self.copies.contains(a))
+            {
+                continue;
+            }
+            if linked.is_some() && linked == fs::canonicalize(&hud.path).ok() {
+                continue;
+            }
+            self.huds.push(hud);
+        }
+
+        for mut hud in disabled {
+            hud.disabled = true;
+            self.huds.push(hud);
+        }
+
+        // the one the active hud was copied from is the same hud
+        let active_source = self.active_hud.as_ref().and_then(|h| h.source.clone());
+        external.retain(|h| {
+            !(active_source.is_some()
+                && h.source == active_source
+                && active_name.as_ref() == Some(&h.name))
+                && (linked.is_none() || linked != fs::canonicalize(&h.path).ok())
+        });
+        self.huds.append(&mut external);
+
+        self.sort();
+        tracing::info!(
+            "found {} huds in `{}`, active: {}",
+            self.huds.len(),
+            custom_dir.display(),
+            self.active_hud.as_ref().map_or("none", |h| h.name.as_str())
+        );
+        let duplicates = self.duplicates();
+        if !duplicates.is_empty() {
+            tracing::warn!("multiple huds share a name: {}", duplicates.join(", "));
+        }
+
+        Ok(self.reconcile_state()?)
+    }
+
+    // catches up with huds moved by something else since the last scan, a hud that turns up in
+    // `custom` is treated as the active one
+    pub fn reconcile_state(&mut self) -> Result<(), HudError> {
+        let custom_dir = self.custom_dir()?;
+
+        for hud in &mut self.huds {
+            let in_custom = custom_dir.join(&hud.name);
+            if !hud.path.exists() && in_custom.join(INFO_VDF).is_file() {
+                hud.path = in_custom;
+            }
+        }
+
+        if self.active_hud.as_ref().is_none_or(|h| !h.path.exists()) {
+            self.active_hud = self
+                .huds
+                .iter()
+                .find(|h| h.path.parent() == Some(custom_dir.as_path()) && h.path.exists())
+                .cloned();
+        }
+
+        Ok(())
+    }
+
+    // folders in `custom` that aren't huds, tf2 loads them all the same so they can get in the
+    // way of the active hud
+    pub fn detect_strays(&self) -> Result<Vec<PathBuf>, HudError> {
+        let custom_dir = self.custom_dir()?;
+
+        let read_dir = fs::read_dir(&custom_dir)
+            .with_context(|| format!("failed to read `{}`", custom_dir.display()))?;
+
+        let mut strays = read_dir
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.is_dir()
+                    && !p.ends_with(HUDS)
+                    && self.active_hud.as_ref().is_none_or(|h| h.path != *p)
+                    && !p.join(INFO_VDF).is_file()
+            })
+            .collect::<Vec<_>>();
+        strays.sort_unstable();
+
+        Ok(strays)
+    }
+
+    pub fn sort_by(&mut self, sort_mode: SortMode) {
+        self.sort_mode = sort_mode;
+        self.sort();
+    }
+
+    pub fn sort(&mut self) {
+        let by_mode = |a: &Hud, b: &Hud| match self.sort_mode {
+            // favorites keep their own order whatever the mode
+            _ if a.favorite && b.favorite => a.cmp(b),
+            SortMode::Name => a.cmp(b),
+            SortMode::ModifiedNewest => b.modified.cmp(&a.modified).then_with(|| a.cmp(b)),
+            SortMode::ModifiedOldest => a.modified.cmp(&b.modified).then_with(|| a.cmp(b)),
+        };
+
+        self.huds
+            .sort_unstable_by(|a, b| b.favorite.cmp(&a.favorite).then_with(|| by_mode(a, b)));
+    }
+
+    pub fn set_active_hud(&mut self, hud: &str) -> Result<(), HudError> {
+        match self.find_switch_target(hud)? {
+            Some(path) => self.set_active_by_path(&path),
+            // nothing to move, but make sure it's still where it's supposed to be
+            None => self.reconcile_state(),
+        }
+    }
+
+    // the moves `set_active_hud` would make, without making them
+    pub fn plan_set_active(&self, hud: &str) -> Result<Vec<PlannedOp>, HudError> {
+        match self.find_switch_target(hud)? {
+            Some(path) => self.plan_set_active_by_path(&path),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn plan_set_active_by_path(&self, path: &Path) -> Result<Vec<PlannedOp>, HudError> {
+        if self.active_hud.as_ref().is_some_and(|h| h.path == path) {
+            return Ok(Vec::new());
+        }
+
+        let (stash, activate) = self.plan_switch(&self.custom_dir()?, path)?;
+        Ok(stash.into_iter().chain(activate).collect())
+    }
+
+    // `None` when it's already active, a stale copy of the active hud left in `huds` doesn't
+    // count as another hud to switch to
+    fn find_switch_target(&self, hud: &str) -> Result<Option<PathBuf>, HudError> {
+        if self.active_hud.as_ref().is_some_and(|h| h.name == hud) {
+            return Ok(None);
+        }
+
+        let mut candidates = self.huds.iter().filter(|h| h.name == hud && !h.disabled);
+
+        match (candidates.next(), candidates.next()) {
+            (Some(h), None) => Ok(Some(h.path.clone())),
+            (Some(_), Some(_)) => Err(HudError::AmbiguousName(hud.to_string())),
+            (None, _) if self.huds.iter().any(|h| h.name == hud && h.disabled) => {
+                Err(HudError::HudDisabled(hud.to_string()))
+            }
+            (None, _) => Err(HudError::HudNotFound(hud.to_string())),
+        }
+    }
+
+    // both the preview and the real switch go through here, so they can't disagree
+    fn plan_switch(
+        &self,
+        custom_dir: &Path,
+        path: &Path,
+    ) -> Result<(Option<PlannedOp>, Option<PlannedOp>)> {
+        let target = self
+            .huds
+            .iter()
+            .find(|h| h.path == path)
+            .ok_or_else(|| HudError::HudNotFound(path.display().to_string()))?;
+        ensure!(
+            target.path.exists(),
+            HudError::HudNotFound(target.name.clone())
+        );
+        ensure!(!target.disabled, HudError::HudDisabled(target.name.clone()));
+        // it would end up as `custom/huds`, which is where everything else lives
+        ensure!(
+            !is_reserved_name(&target.name),
+            HudError::ReservedName(target.name.clone())
+        );
+        storage_dir(custom_dir)?;
+
+        let stash = self.plan_stash(custom_dir)?;
+        let activate = self.plan_activate(custom_dir, path, stash.as_ref())?;
+
+        Ok((stash, activate))
+    }
+
+    // a path picks out one hud even when several share a name
+    pub fn set_active_by_path(&mut self, path: &Path) -> Result<(), HudError> {
+        Ok(self.set_active_path(path)?)
+    }
+
+    fn set_active_path(&mut self, path: &Path) -> Result<()> {
+        let custom_dir = self.custom_dir()?;
+        let _span = tracing::info_span!("switch", to = %path.display()).entered();
+        self.reconcile_state()?;
+        let last_active = self.active_hud.as_ref().map(|h| h.name.clone());
+
+        if self.active_hud.as_ref().map(|h| h.path.as_path()) == Some(path) {
+            return Ok(());
+        }
+        let (stash, activate) = self.plan_switch(&custom_dir, path)?;
+
+        if self.dry_run {
+            log_dry_run(stash.iter().chain(&activate));
+            return Ok(());
+        }
+
+        if let Some(stash) = &stash {
+            self.stash(stash)?;
+        }
+
+        let hud = find_hud(&mut self.huds, path)?;
+        let to = custom_dir.join(&hud.name);
+        if let Some(activate) = &activate {
+            if let Err(e) = activate.execute() {
+                let e = e.context(format!("failed to activate `{}`, {activate}", hud.name));
+                let name = hud.name.clone();
+                return Err(match &stash {
+                    Some(stash) => self.roll_back_stash(&custom_dir, stash, &name, e),
+                    None => e,
+                });
+            }
+            hud.path = to;
+            self.active_hud = Some(hud.clone());
+        } else {
+            tracing::info!("`{}` is already in `custom`, using that copy", hud.name);
+            // something else already put a copy of this hud in `custom`, go with that one
+            self.active_hud = Some(Hud {
+                path: to,
+                ..hud.clone()
+            });
+        }
+        tracing::info!("activated `{}`", hud.name);
+
+        let name = hud.name.clone();
+        let source = hud.source.clone();
+        // kept until now so a roll back could still find where the previous hud came from
+        let previous = last_active.as_ref().filter(|_| stash.is_some());
+        let mut sources_changed = previous.is_some_and(|p| self.sources.remove(p).is_some());
+        let mut copies_changed = previous.is_some_and(|p| self.copies.remove(p));
+        if let Some(source) = source {
+            self.sources.insert(name.clone(), source);
+            sources_changed = true;
+        } else if matches!(activate, Some(PlannedOp::Copy { .. })) {
+            copies_changed |= self.copies.insert(name.clone());
+        }
+        // without them, switching away would move the copy into `huds` rather than remove it
+        let huds_dir = custom_dir.join(HUDS);
+        if sources_changed {
+            if let Err(e) = write_json(&huds_dir, SOURCES_JSON, &self.sources) {
+                tracing::warn!("{e:#}");
+            }
+        }
+        if copies_changed {
+            if let Err(e) = write_json(&huds_dir, COPIES_JSON, &self.copies) {
+                tracing::warn!("{e:#}");
+            }
+        }
+        self.last_active = last_active;
+        self.record_activation(&name);
+
+        Ok(())
+    }
+
+    // the switch already happened, so failing to save when it did isn't worth an error
+    fn record_activation(&mut self, hud: &str) {
+        let now = SystemTime::now();
+
+        self.activated.insert(hud.to_string(), now);
+        for h in self
+            .huds
+            .iter_mut()
+            .chain(&mut self.active_hud)
+            .filter(|h| h.name == hud)
+        {
+            h.last_activated = Some(now);
+        }
+
+        let saved = self
+            .custom_dir()
+            .map_err(anyhow::Error::from)
+            .and_then(|d| write_json(&d.join(HUDS), ACTIVATED_JSON, &self.activated));
+        if let Err(e) = saved {
+            tracing::warn!("{e:#}");
+        }
+    }
+
+    // most recent first, huds that have never been activated aren't included
+    pub fn recently_used(&self, limit: usize) -> Vec<&Hud> {
+        let mut huds = self
+            .huds
+            .iter()
+            .filter(|h| h.last_activated.is_some())
+            .collect::<Vec<_>>();
+        huds.sort_by_key(|h| std::cmp::Reverse(h.last_activated));
+        huds.truncate(limit);
+
+        huds
+    }
+
+    // `None` if a copy of the hud is already sitting in `custom`, so there's nothing to do
+    fn plan_activate(
+        &self,
+        custom_dir: &Path,
+        path: &Path,
+        stash: Option<&PlannedOp>,
+    ) -> Result<Option<PlannedOp>> {
+        let hud = self
+            .huds
+            .iter()
+            .find(|h| h.path == path)
+            .with_context(|| format!("`{}` no longer exists, rescan needed", path.display()))?;
+
+        let to = custom_dir.join(&hud.name);
+        // the active hud is moved out first, so its folder doesn't count
+        let freed = stash.is_some_and(|s| s.source() == to);
+        if !freed && to.join(INFO_VDF).is_file() {
+            return Ok(None);
+        }
+        ensure!(freed || !to.exists(), "`{}` already exists", to.display());
+
+        let from = path.to_path_buf();
+        Ok(Some(match self.activation_strategy {
+            // an extra library is the user's own folder, nothing is ever moved out of it
+            ActivationStrategy::Move if hud.source.is_some() => PlannedOp::Copy { from, to },
+            ActivationStrategy::Move => PlannedOp::Move { from, to },
+            ActivationStrategy::Copy => PlannedOp::Copy { from, to },
+            ActivationStrategy::Symlink => PlannedOp::Link { from, to },
+        }))
+    }
+
+    pub fn toggle_pair(&mut self) -> Result<(), HudError> {
+        let (first, second) = Config::load()?
+            .toggle_pair
+            .with_context(|| "no `toggle_pair` set in the config")?;
+
+        if self.active_hud.as_ref().is_some_and(|h| h.name == first) {
+            self.set_active_hud(&second)
+        } else {
+            self.set_active_hud(&first)
+        }
+    }
+
+    pub fn deactivate(&mut self) -> Result<(), HudError> {
+        let custom_dir = self.custom_dir()?;
+
+        let Some(active_hud) = &self.active_hud else {
+            return Ok(());
+        };
+        let last_active = active_hud.name.clone();
+
+        let stash = self.plan_stash(&custom_dir)?;
+        if self.dry_run {
+            log_dry_run(&stash);
+            return Ok(());
+        }
+
+        if let Some(stash) = &stash {
+            self.stash(stash)?;
+        }
+        if self.sources.remove(&last_active).is_some() {
+            write_json(&custom_dir.join(HUDS), SOURCES_JSON, &self.sources)?;
+        }
+        if self.copies.remove(&last_active) {
+            write_json(&custom_dir.join(HUDS), COPIES_JSON, &self.copies)?;
+        }
+        self.active_hud = None;
+        self.last_active = Some(last_active);
+
+        Ok(())
+    }
+
+    fn plan_stash(&self, custom_dir: &Path) -> Result<Option<PlannedOp>> {
+        let Some(active_hud) = self.active_hud.as_ref().filter(|h| h.path.exists()) else {
+            return Ok(None);
+        };
+
+        let to = self
+            .library_dir(custom_dir, &active_hud.name)
+            .join(&active_hud.name);
+        let from = active_hud.path.clone();
+        // goes by what's there and what was recorded when it was activated rather than the
+        // strategy, which may have changed since
+        let copied = self.is_copy(&active_hud.name);
+        if is_link(&from) {
+            return Ok(Some(PlannedOp::Unlink { path: from }));
+        }
+        // only the copy goes, a hud that was never in the library is moved back as usual
+        if copied && to.join(INFO_VDF).is_file() {
+            return Ok(Some(PlannedOp::Remove { path: from }));
+        }
+        ensure!(
+            !to.exists(),
+            "can't move `{}` back, `{}` already exists",
+            active_hud.name,
+            to.display()
+        );
+
+        Ok(Some(PlannedOp::Move { from, to }))
+    }
+
+    // a library hud is always a copy or a link, `sources` already says which library
+    fn is_copy(&self, hud: &str) -> bool {
+        self.copies.contains(hud) || self.sources.contains_key(hud)
+    }
+
+    // where the hud goes back to when it's switched away from
+    fn library_dir(&self, custom_dir: &Path, hud: &str) -> PathBuf {
+        self.sources
+            .get(hud)
+            .cloned()
+            .unwrap_or_else(|| custom_dir.join(HUDS))
+    }
+
+    // puts the previously active hud back when the new one couldn't be activated, so the game
+    // isn't left without a hud
+    fn roll_back_stash(
+        &mut self,
+        custom_dir: &Path,
+        stash: &PlannedOp,
+        failed: &str,
+        error: anyhow::Error,
+    ) -> anyhow::Error {
+        let Some(previous) = self.active_hud.as_ref().map(|h| h.name.clone()) else {
+            return error;
+        };
+        let library = self.library_dir(custom_dir, &previous).join(&previous);
+        let undo = match stash {
+            PlannedOp::Move { from, to } => PlannedOp::Move {
+                from: to.clone(),
+                to: from.clone(),
+            },
+            PlannedOp::Remove { path } => PlannedOp::Copy {
+                from: library.clone(),
+                to: path.clone(),
+            },
+            PlannedOp::Unlink { path } => PlannedOp::Link {
+                from: library.clone(),
+                to: path.clone(),
+            },
+            PlannedOp::Copy { .. } | PlannedOp::Link { .. } | PlannedOp::Trash { .. } => {
+                return error
+            }
+        };
+
+        if let Err(undo_error) = undo.execute() {
+            tracing::error!("failed to roll back, {undo}: {undo_error:#}");
+            return error.context(format!(
+                "`{previous}` couldn't be put back either and is in `{}`, no hud is active",
+                library.display()
+            ));
+        }
+        tracing::info!("rolled back, {undo}");
+        if let Ok(hud) = find_hud(&mut self.huds, &library) {
+            hud.path = stash.source().to_path_buf();
+        }
+
+        error.context(format!(
+            "`{previous}` is still the active hud, `{failed}` was left in `huds`"
+        ))
+    }
+
+    fn stash(&mut self, stash: &PlannedOp) -> Result<()> {
+        let custom_dir = self.custom_dir()?;
+        let name = find_hud(&mut self.huds, stash.source())?.name.clone();
+        let library_dir = self.library_dir(&custom_dir, &name);
+
+        if self.backup_zips > 0 {
+            let backups_dir = custom_dir.join(HUDS).join(backup::BACKUPS).join(&name);
+            let zip = backup::zip_backup(stash.source(), &name, &backups_dir)
+                .with_context(|| format!("failed to back up `{name}`"))?;
+            tracing::info!("backed up `{name}` to `{}`", zip.display());
+            if let Err(e) = backup::prune_zips(&backups_dir, self.backup_zips) {
+                tracing::warn!("{e:#}");
+            }
+        }
+
+        let hud = find_hud(&mut self.huds, stash.source())?;
+
+        if self.backup_before_switch {
+            let backups_dir = custom_dir.join(HUDS).join(backup::BACKUPS);
+            backup::backup_files(
+                stash.source(),
+                &backups_dir.join(&hud.name),
+                &self.backup_allowlist,
+            )
+            .with_context(|| format!("failed to back up `{}`", hud.name))?;
+        }
+
+        stash
+            .execute()
+            .with_context(|| format!("failed to move `{}` out of the way, {stash}", hud.name))?;
+        hud.path = library_dir.join(&hud.name);
+
+        Ok(())
+    }
+
+    pub fn disable_hud(&mut self, hud: &str) -> Result<(), HudError> {
+        let disabled_dir = self.custom_dir()?.join(HUDS).join(DISABLED);
+        Ok(self.set_disabled(hud, true, &disabled_dir)?)
+    }
+
+    pub fn enable_hud(&mut self, hud: &str) -> Result<(), HudError> {
+        let huds_dir = self.custom_dir()?.join(HUDS);
+        Ok(self.set_disabled(hud, false, &huds_dir)?)
+    }
+
+    fn set_disabled(&mut self, hud: &str, disabled: bool, to_dir: &Path) -> Result<()> {
+        let hud = self
+            .huds
+            .iter()
+            .find(|h| h.name == hud && h.disabled != disabled)
+            .ok_or_else(|| HudError::HudNotFound(hud.to_string()))?;
+
+        ensure!(
+            self.active_hud.as_ref().is_none_or(|h| h.path != hud.path),
+            HudError::HudAlreadyActive {
+                hud: hud.name.clone(),
+                action: "disabling",
+            }
+        );
+
+        ensure_not_in_library(hud, "disabled")?;
+
+        let from = hud.path.clone();
+        let to = to_dir.join(&hud.name);
+        ensure!(!to.exists(), "`{}` already exists", to.display());
+
+        let op = PlannedOp::Move {
+            from: from.clone(),
+            to: to.clone(),
+        };
+        if self.dry_run {
+            log_dry_run([&op]);
+            return Ok(());
+        }
+
+        fs::create_dir_all(to_dir)?;
+        let hud = find_hud(&mut self.huds, &from)?;
+        op.execute().with_context(|| {
+            let action = if disabled { "disable" } else { "enable" };
+            format!("failed to {action} `{}`, {op}", hud.name)
+        })?;
+        hud.path = to;
+        hud.disabled = disabled;
+
+        Ok(())
+    }
+
+    // goes to the trash where there is one, so a mistake can still be undone
+    pub fn delete_hud(&mut self, hud: &str) -> Result<(), HudError> {
+        let mut candidates = self.huds.iter().filter(|h| h.name == hud);
+        let hud = match (candidates.next(), candidates.next()) {
+            (Some(h), None) => h,
+            (Some(_), Some(_)) => return Err(HudError::AmbiguousName(hud.to_string())),
+            (None, _) => return Err(HudError::HudNotFound(hud.to_string())),
+        };
+
+        if self.active_hud.as_ref().is_some_and(|h| h.path == hud.path) {
+            return Err(HudError::HudAlreadyActive {
+                hud: hud.name.clone(),
+                action: "deleting",
+            });
+        }
+        ensure_not_in_library(hud, "deleted")?;
+
+        let op = PlannedOp::Trash {
+            path: hud.path.clone(),
+        };
+        if self.dry_run {
+            log_dry_run([&op]);
+            return Ok(());
+        }
+
+        op.execute()
+            .with_context(|| format!("failed to delete `{}`", hud.name))?;
+
+        let name = hud.name.clone();
+        let key = favorite_key(&name);
+        self.huds.retain(|h| h.path != op.source());
+        self.favorites.retain(|f| *f != key);
+
+        // a new hud installed under the same name shouldn't inherit any of this
+        let huds_dir = self.custom_dir()?.join(HUDS);
+        if self.notes.remove(&name).is_some() {
+            write_json(&huds_dir, NOTES_JSON, &self.notes)?;
+        }
+        if self.tags.remove(&name).is_some() {
+            self.save_tags()?;
+        }
+        if self.activated.remove(&name).is_some() {
+            write_json(&huds_dir, ACTIVATED_JSON, &self.activated)?;
+        }
+
+        self.save_favorites()
+    }
+
+    pub fn rename_hud(&mut self, old: &str, new: &str) -> Result<(), HudError> {
+        let custom_dir = self.custom_dir()?;
+
+        let mut candidates = self.huds.iter().filter(|h| h.name == old);
+        let hud = match (candidates.next(), candidates.next()) {
+            (Some(h), None) => h,
+            (Some(_), Some(_)) => return Err(HudError::AmbiguousName(old.to_string())),
+            (None, _) => return Err(HudError::HudNotFound(old.to_string())),
+        };
+
+        validate_folder_name(new)?;
+        ensure_not_in_library(hud, "renamed")?;
+        if new == old {
+            return Ok(());
+        }
+        // a change of casing is still the same folder as far as windows is concerned
+        let same_folder = favorite_key(new) == favorite_key(old);
+        if self.huds.iter().any(|h| h.name == new)
+            || (!same_folder
+                && (custom_dir.join(HUDS).join(new).exists() || custom_dir.join(new).exists()))
+        {
+            return Err(HudError::NameCollision(new.to_string()));
+        }
+
+        let from = hud.path.clone();
+        let is_active = self.active_hud.as_ref().is_some_and(|h| h.path == from);
+        if !same_folder && from.with_file_name(new).exists() {
+            return Err(HudError::NameCollision(new.to_string()));
+        }
+        let library_copy = custom_dir.join(HUDS).join(old);
+        let rename_library = PlannedOp::Move {
+            from: library_copy.clone(),
+            to: library_copy.with_file_name(new),
+        };
+        let ops = if is_link(&from) {
+            // a link can't be renamed in place, it would point at the old name
+            vec![
+                PlannedOp::Unlink { path: from.clone() },
+                rename_library,
+                PlannedOp::Link {
+                    from: library_copy.with_file_name(new),
+                    to: from.with_file_name(new),
+                },
+            ]
+        } else {
+            let mut ops = vec![PlannedOp::Move {
+                from: from.clone(),
+                to: from.with_file_name(new),
+            }];
+            // the library copy has to follow or the next scan would list it as a separate hud
+            if is_active && self.copies.contains(old) && library_copy.join(INFO_VDF).is_file() {
+                ops.push(rename_library);
+            }
+            ops
+        };
+
+        if self.dry_run {
+            log_dry_run(&ops);
+            return Ok(());
+        }
+
+        for op in &ops {
+            op.execute()
+                .with_context(|| format!("failed to rename `{old}`, {op}"))?;
+        }
+
+        let hud = find_hud(&mut self.huds, &from)?;
+        hud.name = new.to_string();
+        hud.path = from.with_file_name(new);
+        if is_active {
+            self.active_hud = Some(hud.clone());
+        }
+        if self.last_active.as_deref() == Some(old) {
+            self.last_active = Some(new.to_string());
+        }
+
+        if let Some(activated) = self.activated.remove(old) {
+            self.activated.insert(new.to_string(), activated);
+            write_json(&custom_dir.join(HUDS), ACTIVATED_JSON, &self.activated)?;
+        }
+        if self.copies.remove(old) {
+            self.copies.insert(new.to_string());
+            write_json(&custom_dir.join(HUDS), COPIES_JSON, &self.copies)?;
+        }
+        if let Some(tags) = self.tags.remove(old) {
+            self.tags.insert(new.to_string(), tags);
+            self.save_tags()?;
+        }
+        if let Some(note) = self.notes.remove(old) {
+            self.notes.insert(new.to_string(), note);
+            write_json(&custom_dir.join(HUDS), NOTES_JSON, &self.notes)?;
+        }
+
+        let (old_key, new_key) = (favorite_key(old), favorite_key(new));
+        if let Some(key) = self.favorites.iter_mut().find(|f| **f == old_key) {
+            *key = new_key;
+            self.save_favorites()?;
+        }
+        self.sort();
+
+        Ok(())
+    }
+
+    pub fn duplicates(&self) -> Vec<&str> {
+        let mut names = self
+            .huds
+            .iter()
+            .map(|h| h.name.as_str())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+
+        let mut duplicates = names
+            .windows(2)
+            .filter(|w| w[0] == w[1])
+            .map(|w| w[0])
+            .collect::<Vec<_>>();
+        duplicates.dedup();
+
+        duplicates
+    }
+
+    pub fn undo_set_active(&mut self) -> Result<(), HudError> {
+        match self.last_active.clone() {
+            Some(hud) => self.set_active_hud(&hud),
+            None => Ok(()),
+        }
+    }
+
+    // `to` is the folder the zip goes in, the zip itself is named after the hud and when it was made
+    pub fn backup_active(&self, to: &Path) -> Result<PathBuf, HudError> {
+        let hud = self
+            .active_hud
+            .as_ref()
+            .filter(|h| h.path.exists())
+            .with_context(|| "there's no active hud to back up")?;
+
+        let zip = backup::zip_backup(&hud.path, &hud.name, to)
+            .with_context(|| format!("failed to back up `{}`", hud.name))?;
+        tracing::info!("backed up `{}` to `{}`", hud.name, zip.display());
+
+        Ok(zip)
+    }
+
+    pub fn list_backups(&self, hud: &str) -> Vec<PathBuf> {
+        let Ok(custom_dir) = self.custom_dir() else {
+            return Vec::new();
+        };
+
+        backup::list_backups(&custom_dir.join(HUDS).join(backup::BACKUPS).join(hud))
+    }
+
+    pub fn save_favorites(&mut self) -> Result<(), HudError> {
+        let huds_dir = storage_dir(&self.custom_dir()?)?;
+
+        // unfavorited huds are dropped, favorites that aren't installed right now are kept
+        self.favorites.retain(|f| {
+            self.huds
+                .iter()
+                .all(|h| favorite_key(&h.name) != *f || h.favorite)
+        });
+        for hud in self.huds.iter().filter(|h| h.favorite) {
+            let key = favorite_key(&hud.name);
+            if !self.favorites.contains(&key) {
+                self.favorites.push(key);
+            }
+        }
+        for hud in &mut self.huds {
+            let key = favorite_key(&hud.name);
+            hud.favorite_rank = self.favorites.iter().position(|f| *f == key);
+        }
+
+        let file = FavoritesFile::new(self.favorites.iter().cloned());
+        write_json(&huds_dir, FAVORITES_JSON, &file)?;
+        tracing::debug!("saved {} favorites", self.favorites.len());
+
+        Ok(())
+    }
+
+    pub fn set_favorite(&mut self, hud: &str, favorite: bool) -> Result<(), HudError> {
+        let Some(hud) = self.huds.iter_mut().find(|h| h.name == hud) else {
+            return Err(HudError::HudNotFound(hud.to_string()));
+        };
+
+        hud.favorite = favorite;
+        self.save_favorites()
+    }
+
+    // in their stored order, favorites that aren't installed right now come out as they're stored
+    pub fn favorites(&self) -> impl Iterator<Item = &str> {
+        self.favorites.iter().map(|key| {
+            self.huds
+                .iter()
+                .find(|h| favorite_key(&h.name) == *key)
+                .map_or(key.as_str(), |h| h.name.as_str())
+        })
+    }
+
+    // saving keeps favorites that aren't installed right now, this forgets them for good
+    pub fn prune_favorites(&mut self) -> Result<usize, HudError> {
+        let before = self.favorites.len();
+        self.favorites
+            .retain(|key| self.huds.iter().any(|h| favorite_key(&h.name) == *key));
+
+        let pruned = before - self.favorites.len();
+        if pruned > 0 {
+            tracing::info!("forgot {pruned} favorites that aren't installed");
+        }
+        self.save_favorites()?;
+
+        Ok(pruned)
+    }
+
+    pub fn move_favorite_up(&mut self, hud: &str) -> Result<(), HudError> {
+        Ok(self.shift_favorite(hud, true)?)
+    }
+
+    pub fn move_favorite_down(&mut self, hud: &str) -> Result<(), HudError> {
+        Ok(self.shift_favorite(hud, false)?)
+    }
+
+    // `new_index` is a position in `favorites()`, past the end moves it to the bottom
+    pub fn move_favorite(&mut self, hud: &str, new_index: usize) -> Result<(), HudError> {
+        let key = favorite_key(hud);
+        let i = self
+            .favorites
+            .iter()
+            .position(|f| *f == key)
+            .with_context(|| format!("`{hud}` isn't a favorite"))?;
+
+        let key = self.favorites.remove(i);
+        let new_index = new_index.min(self.favorites.len());
+        self.favorites.insert(new_index, key);
+
+        self.save_favorites()?;
+        self.sort();
+
+        Ok(())
+    }
+
+    fn shift_favorite(&mut self, hud: &str, up: bool) -> Result<()> {
+        let i = self
+            .huds
+            .iter()
+            .position(|h| h.favorite && h.name == hud)
+            .with_context(|| format!("`{hud}` isn't a favorite"))?;
+
+        let j = if up { i.checked_sub(1) } else { Some(i + 1) };
+        let Some(j) = j.filter(|&j| self.huds.get(j).is_some_and(|h| h.favorite)) else {
+            return Ok(());
+        };
+
+        let position = |name: &str| {
+            let key = favorite_key(name);
+            self.favorites.iter().position(|f| *f == key)
+        };
+        if let (Some(a), Some(b)) = (position(&self.huds[i].name), position(&self.huds[j].name)) {
+            self.favorites.swap(a, b);
+        }
+
+        self.save_favorites()?;
+        self.sort();
+
+        Ok(())
+    }
+
+    pub fn update_favorites(&mut self) -> Result<(), HudError> {
+        let huds_dir = self.custom_dir()?.join(HUDS);
+        let legacy = huds_dir.join(FAVORITES_TXT);
+
+        self.tags = read_json(&huds_dir, TAGS_JSON)?;
+        self.notes = read_json(&huds_dir, NOTES_JSON)?;
+        self.activated = read_json(&huds_dir, ACTIVATED_JSON)?;
+        self.sources = read_json(&huds_dir, SOURCES_JSON)?;
+        self.copies = read_json(&huds_dir, COPIES_JSON)?;
+        let config = Config::load()?;
+        self.aliases = config.aliases;
+        self.libraries = config.libraries;
+        self.backup_zips = config.backup_zips.unwrap_or(0);
+        self.activation_strategy = config.activation_strategy.unwrap_or_default();
+        self.backup_before_switch = config.backup_before_switch;
+        self.backup_allowlist = config
+            .backup_allowlist
+            .unwrap_or_else(backup::default_allowlist);
+
+        let (names, migrated) = if huds_dir.join(FAVORITES_JSON).exists() {
+            let file: FavoritesFile = read_json(&huds_dir, FAVORITES_JSON)?;
+            if file.version > FAVORITES_VERSION {
+                tracing::warn!(
+                    "`{FAVORITES_JSON}` is from a newer version ({}), some of it may be ignored",
+                    file.version
+                );
+            }
+            (file.names().collect::<Vec<_>>(), false)
+        } else if legacy.exists() {
+            let contents = fs::read_to_string(&legacy)
+                .with_context(|| format!("failed to read `{}`", legacy.display()))?;
+            (contents.lines().map(str::to_string).collect(), true)
+        } else {
+            fs::create_dir_all(huds_dir)?;
+            return Ok(());
+        };
+
+        self.favorites.clear();
+        for key in names.iter().map(|n| favorite_key(n)) {
+            if !key.is_empty() && !self.favorites.contains(&key) {
+                self.favorites.push(key);
+            }
+        }
+        tracing::debug!("loaded {} favorites", self.favorites.len());
+
+        // `favorites.txt` is left where it is, so going back to an older version doesn't lose them
+        if migrated {
+            let file = FavoritesFile::new(self.favorites.iter().cloned());
+            write_json(&huds_dir, FAVORITES_JSON, &file)?;
+            tracing::info!("moved favorites from `{FAVORITES_TXT}` to `{FAVORITES_JSON}`");
+        }
+
+        Ok(())
+    }
+
+    fn save_tags(&self) -> Result<()> {
+        write_json(&self.custom_dir()?.join(HUDS), TAGS_JSON, &self.tags)
+    }
+
+    pub fn set_tags(&mut self, hud: &str, tags: Vec<String>) -> Result<(), HudError> {
+        let mut tags = tags
+            .into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+
+        for h in self.huds.iter_mut().filter(|h| h.name == hud) {
+            h.tags = tags.clone();
+        }
+        if tags.is_empty() {
+            self.tags.remove(hud);
+        } else {
+            self.tags.insert(hud.to_string(), tags);
+        }
 
-#[derive(Default)]
-pub struct Huds {
-    pub huds: Vec<Hud>,
-    pub active_hud: Option<Hud>,
-    favorites: HashSet<String>,
-}
+        Ok(self.save_tags()?)
+    }
 
-impl Huds {
-    pub fn scan_for_huds(&mut self) -> Result<()> {
-        let custom_dir = custom_dir()?;
+    pub fn add_tag(&mut self, hud: &str, tag: &str) -> Result<(), HudError> {
+        let mut tags = self.tags.get(hud).cloned().unwrap_or_default();
+        tags.push(tag.to_string());
 
-        self.huds.clear();
+        self.set_tags(hud, tags)
+    }
 
-        let walk_dir = |d| WalkDir::new(d).max_depth(2).into_iter().flatten();
-        let is_vdf = |e: &walkdir::DirEntry| e.path().ends_with(INFO_VDF);
-        let hud_from_vdf = |e| {
-            let mut hud = Hud::from_vdf(e);
-            hud.favorite = self.favorites.contains(&hud.name);
-            hud
+    pub fn remove_tag(&mut self, hud: &str, tag: &str) -> Result<(), HudError> {
+        let mut tags = self.tags.get(hud).cloned().unwrap_or_default();
+        tags.retain(|t| t != tag);
+
+        self.set_tags(hud, tags)
+    }
+
+    pub fn all_tags(&self) -> Vec<&str> {
+        let mut tags = self
+            .huds
+            .iter()
+            .flat_map(|h| &h.tags)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+
+        tags
+    }
+
+    pub fn get_note(&self, hud: &str) -> Option<&str> {
+        self.notes.get(hud).map(String::as_str)
+    }
+
+    pub fn set_note(&mut self, hud: &str, note: &str) -> Result<(), HudError> {
+        let note = Some(note.to_string()).filter(|n| !n.trim().is_empty());
+
+        match &note {
+            Some(note) => self.notes.insert(hud.to_string(), note.clone()),
+            None => self.notes.remove(hud),
         };
+        for h in self
+            .huds
+            .iter_mut()
+            .chain(&mut self.active_hud)
+            .filter(|h| h.name == hud)
+        {
+            h.note = note.clone();
+        }
 
-        if let Some(entry) = walk_dir(&custom_dir).find(is_vdf) {
-            let hud = hud_from_vdf(entry.into_path());
-            self.huds.push(hud.clone());
-            self.active_hud = Some(hud);
+        Ok(write_json(
+            &self.custom_dir()?.join(HUDS),
+            NOTES_JSON,
+            &self.notes,
+        )?)
+    }
+
+    pub fn aliases(&self, hud: &str) -> &[String] {
+        self.aliases.get(hud).map_or(&[], Vec::as_slice)
+    }
+
+    // best match first, a hud is kept when it scores at least `threshold` of the best match, so
+    // 0.0 keeps everything that matches at all and 1.0 only the best
+    pub fn search(&self, query: &str, threshold: f32) -> Vec<&Hud> {
+        if query.trim().is_empty() {
+            return self.huds.iter().collect();
         }
 
-        for entry in walk_dir(&custom_dir.join(HUDS)).filter(is_vdf) {
-            let hud = hud_from_vdf(entry.into_path());
-            self.huds.push(hud);
+        let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Never);
+        let mut matcher = Matcher::default();
+        let mut buf = Vec::new();
+
+        let mut results = self
+            .huds
+            .iter()
+            .filter_map(|hud| {
+                let haystack = [
+                    Some(hud.name.as_str()),
+                    hud.display_name.as_deref(),
+                    hud.author.as_deref(),
+                ]
+                .into_iter()
+                .flatten()
+                .chain(self.aliases(&hud.name).iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+                pattern
+                    .score(Utf32Str::new(&haystack, &mut buf), &mut matcher)
+                    .map(|score| (hud, score))
+            })
+            .collect::<Vec<_>>();
+        results.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+
+        let Some(&(_, best)) = results.first() else {
+            return Vec::new();
+        };
+
+        results
+            .into_iter()
+            .filter(|&(_, score)| best == 0 || score as f32 / best as f32 >= threshold)
+            .map(|(hud, _)| hud)
+            .collect()
+    }
+
+    // the active hud is left alone unless `allow_active`, updating it may change files while
+    // the game is using them
+    pub fn update_hud(&self, hud: &str, allow_active: bool) -> Result<PullOutcome, HudError> {
+        let hud = self
+            .huds
+            .iter()
+            .find(|h| h.name == hud)
+            .ok_or_else(|| HudError::HudNotFound(hud.to_string()))?;
+
+        let is_active = self.active_hud.as_ref().is_some_and(|h| h.path == hud.path);
+        if is_active && !allow_active {
+            return Err(HudError::HudAlreadyActive {
+                hud: hud.name.clone(),
+                action: "updating",
+            });
+        }
+
+        let outcome = hud.git_pull()?;
+
+        // copying leaves the library checkout in `huds`, which is what gets copied back in next
+        // time, so it has to be updated too or the update is lost on the next switch
+        let library = self
+            .library_dir(&self.custom_dir()?, &hud.name)
+            .join(&hud.name);
+        if is_active && self.is_copy(&hud.name) && library.join(".git").exists() {
+            let library = Hud {
+                path: library,
+                ..hud.clone()
+            };
+            library.git_pull()?;
         }
 
-        self.huds.sort_unstable();
+        Ok(outcome)
+    }
+
+    pub fn export_favorites(&self, path: &Path) -> Result<(), HudError> {
+        let file = FavoritesFile::new(self.favorites().map(str::to_string));
+        let favorites = serde_json::to_string_pretty(&file).map_err(anyhow::Error::from)?;
+        fs::write(path, favorites)
+            .with_context(|| format!("failed to write `{}`", path.display()))?;
 
         Ok(())
     }
 
-    pub fn set_active_hud(&mut self, hud: &str) -> Result<()> {
-        let custom_dir = custom_dir()?;
+    // returns the imported favorites that aren't installed here, they're left out
+    pub fn import_favorites(&mut self, path: &Path, merge: bool) -> Result<Vec<String>, HudError> {
+        let favorites = fs::read_to_string(path)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+        let favorites = match serde_json::from_str(&favorites)
+            .with_context(|| format!("`{}` isn't a favorites file", path.display()))?
+        {
+            Favorites::File(file) => file.names().collect(),
+            Favorites::Unversioned { favorites } | Favorites::List(favorites) => favorites,
+        };
 
-        if let Some(active_hud) = self.active_hud.as_ref().filter(|h| h.path.exists()) {
-            ensure!(hud != active_hud.name, "hud already active");
+        if !merge {
+            self.favorites.clear();
+            for hud in &mut self.huds {
+                hud.favorite = false;
+            }
+        }
 
-            let to = custom_dir.join(format!("{HUDS}/{}", active_hud.name));
-            fs::rename(&active_hud.path, &to).with_context(|| "failed to move hud")?;
+        let mut missing = Vec::new();
+        for name in favorites {
+            let key = favorite_key(&name);
+            let mut installed = false;
+            for hud in self
+                .huds
+                .iter_mut()
+                .filter(|h| favorite_key(&h.name) == key)
+            {
+                hud.favorite = true;
+                installed = true;
+            }
 
-            let hud = find_hud(&mut self.huds, &active_hud.name);
-            hud.path = to;
+            if !installed {
+                if !key.is_empty() {
+                    missing.push(name);
+                }
+            } else if !self.favorites.contains(&key) {
+                self.favorites.push(key);
+            }
+        }
+        if !missing.is_empty() {
+            tracing::info!(
+                "left out favorites that aren't installed: {}",
+                missing.join(", ")
+            );
         }
 
-        let hud = find_hud(&mut self.huds, hud);
+        self.save_favorites()?;
+        self.sort();
 
-        let to = custom_dir.join(&hud.name);
-        fs::rename(&hud.path, &to).with_context(|| "failed to move hud")?;
+        Ok(missing)
+    }
 
-        hud.path = to;
-        self.active_hud = Some(hud.clone());
+    // paths are saved relative to `custom` so the file works on another machine
+    pub fn export_state(&self, path: &Path) -> Result<(), HudError> {
+        let custom_dir = self.custom_dir()?;
+        let relative = |hud: &Hud| Hud {
+            path: hud
+                .path
+                .strip_prefix(&custom_dir)
+                .map_or_else(|_| hud.path.clone(), portable_path),
+            ..hud.clone()
+        };
 
-        fn find_hud<'a>(huds: &'a mut [Hud], hud: &str) -> &'a mut Hud {
-            huds.iter_mut()
-                .find(|h| h.name == hud)
-                .expect("hud should exist")
-        }
+        let state = Self {
+            huds: self.huds.iter().map(relative).collect(),
+            active_hud: self.active_hud.as_ref().map(relative),
+            sort_mode: self.sort_mode,
+            backup_before_switch: self.backup_before_switch,
+            backup_allowlist: self.backup_allowlist.clone(),
+            activation_strategy: self.activation_strategy,
+            favorites: self.favorites.clone(),
+            tags: self.tags.clone(),
+            notes: self.notes.clone(),
+            ..Self::default()
+        };
+
+        let state = serde_json::to_string_pretty(&state).map_err(anyhow::Error::from)?;
+        fs::write(path, state).with_context(|| format!("failed to write `{}`", path.display()))?;
 
         Ok(())
     }
 
-    pub fn save_favorites(&mut self) -> Result<()> {
-        let huds_dir = custom_dir()?.join(HUDS);
-        let favorites = huds_dir.join(FAVORITES_TXT);
+    // favorites, tags and notes are restored, the huds themselves are whatever is on disk
+    pub fn import_state(&mut self, path: &Path) -> Result<(), HudError> {
+        let huds_dir = self.custom_dir()?.join(HUDS);
 
-        if !huds_dir.exists() {
-            fs::create_dir_all(huds_dir)?;
-        }
+        let state = fs::read_to_string(path)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+        let state: Self = serde_json::from_str(&state)
+            .with_context(|| format!("`{}` isn't a hud manager export", path.display()))?;
 
-        let mut file = fs::File::create(favorites)?;
-        self.favorites.clear();
-        file.write_all(
-            self.huds
-                .iter()
-                .take_while(|h| h.favorite)
-                .map(|h| h.name.as_str())
-                .inspect(|h| {
-                    self.favorites.insert(h.to_string());
-                })
-                .collect::<Vec<_>>()
-                .join("\n")
-                .as_bytes(),
-        )?;
+        self.favorites = state.favorites;
+        self.tags = state.tags;
+        self.notes = state.notes;
+
+        self.scan_for_huds()?;
+        self.save_favorites()?;
+        self.save_tags()?;
+        write_json(&huds_dir, NOTES_JSON, &self.notes)?;
 
         Ok(())
     }
 
-    pub fn update_favorites(&mut self) -> Result<()> {
-        let huds_dir = custom_dir()?.join(HUDS);
-        let favorites = huds_dir.join(FAVORITES_TXT);
+    pub fn watch(&self, on_event: impl Fn(Duration) + Send + 'static) -> Result<Watcher, HudError> {
+        let custom_dir = self.custom_dir()?;
 
-        if !favorites.exists() {
-            fs::create_dir_all(huds_dir)?;
-            fs::File::create(&favorites)?;
-            return Ok(());
+        let huds_dir = custom_dir.join(HUDS);
+        let dirs = [&custom_dir, &huds_dir]
+            .into_iter()
+            .chain(&self.libraries)
+            .map(PathBuf::as_path)
+            .collect::<Vec<_>>();
+
+        Ok(Watcher::new(&dirs, on_event)?)
+    }
+
+    pub fn duplicate_hud(&mut self, hud: &str, new_name: &str) -> Result<(), HudError> {
+        let custom_dir = self.custom_dir()?;
+
+        let hud = self
+            .huds
+            .iter()
+            .find(|h| h.name == hud)
+            .ok_or_else(|| HudError::HudNotFound(hud.to_string()))?;
+
+        validate_folder_name(new_name)?;
+        let to = custom_dir.join(HUDS).join(new_name);
+        if to.exists()
+            || custom_dir.join(new_name).exists()
+            || self.huds.iter().any(|h| h.name == new_name)
+        {
+            return Err(HudError::NameCollision(new_name.to_string()));
         }
 
-        let favorites =
-            fs::read_to_string(favorites).with_context(|| "failed to read `favorites.txt`")?;
+        if let Err(e) = copy_dir_all(&hud.path, &to) {
+            let _ = fs::remove_dir_all(&to);
+            let e = e.context(format!("failed to copy `{}` to `{new_name}`", hud.name));
+            return Err(e.into());
+        }
 
-        self.favorites = favorites.lines().map(|l| l.to_string()).collect();
+        self.scan_for_huds()
+    }
 
-        Ok(())
+    pub fn install_from_zip(
+        &mut self,
+        archive: &Path,
+        existing: ExistingHud,
+        progress: impl FnMut(usize, usize),
+    ) -> Result<String, HudError> {
+        let file = fs::File::open(archive)
+            .with_context(|| format!("failed to open `{}`", archive.display()))?;
+        let stem = archive
+            .file_stem()
+            .with_context(|| format!("`{}` isn't a file", archive.display()))?
+            .to_string_lossy()
+            .to_string();
+
+        let name = |root: Option<&Path>| {
+            let name = root
+                .and_then(Path::file_name)
+                .map_or(stem, |n| n.to_string_lossy().to_string());
+            strip_branch_suffix(&name).to_string()
+        };
+
+        Ok(self.install_zip(
+            file,
+            &archive.display().to_string(),
+            name,
+            existing,
+            progress,
+        )?)
+    }
+
+    pub fn install_from_github(
+        &mut self,
+        url: &str,
+        existing: ExistingHud,
+        progress: impl FnMut(usize, usize),
+    ) -> Result<String, HudError> {
+        let (repo, zip) = github::download_zip(url)?;
+
+        Ok(self.install_zip(std::io::Cursor::new(zip), url, |_| repo, existing, progress)?)
+    }
+
+    // `name` gets the folder the `info.vdf` was found in, or `None` if it was at the root,
+    // `progress` gets the number of extracted entries and the total
+    fn install_zip<R: Read + Seek>(
+        &mut self,
+        reader: R,
+        source: &str,
+        name: impl FnOnce(Option<&Path>) -> String,
+        existing: ExistingHud,
+        progress: impl FnMut(usize, usize),
+    ) -> Result<String> {
+        let custom_dir = self.custom_dir()?;
+        let huds_dir = custom_dir.join(HUDS);
+
+        let mut zip = zip::ZipArchive::new(reader)
+            .with_context(|| format!("`{source}` is not a valid zip"))?;
+
+        ensure!(
+            zip.file_names()
+                .flatten()
+                .any(|f| Path::new(f.as_ref()).ends_with(INFO_VDF)),
+            "`{source}` doesn't contain an `{INFO_VDF}`"
+        );
+
+        let tmp = huds_dir.join(INSTALL_TMP);
+        if tmp.exists() {
+            fs::remove_dir_all(&tmp)?;
+        }
+        fs::create_dir_all(&tmp)?;
+
+        let installed = extract_hud(&mut zip, source, &tmp, progress).and_then(|root| {
+            let name = name(Some(root.as_path()).filter(|r| *r != tmp));
+            place_hud(&root, &custom_dir, name, existing)
+        });
+        // a hud at the root of the zip is the install folder itself, so it's already gone
+        if tmp.exists() {
+            fs::remove_dir_all(&tmp).with_context(|| "failed to clean up install folder")?;
+        }
+        let name = installed?;
+
+        self.scan_for_huds()?;
+
+        Ok(name)
+    }
+}
+
+// a filesystem change worked out ahead of time, so it can be checked and logged in dry run mode
+// before anything is touched
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlannedOp {
+    Move { from: PathBuf, to: PathBuf },
+    Copy { from: PathBuf, to: PathBuf },
+    Remove { path: PathBuf },
+    Trash { path: PathBuf },
+    // `to` ends up a link to `from`
+    Link { from: PathBuf, to: PathBuf },
+    Unlink { path: PathBuf },
+}
+
+impl PlannedOp {
+    fn source(&self) -> &Path {
+        match self {
+            Self::Move { from, .. } | Self::Copy { from, .. } | Self::Link { from, .. } => from,
+            Self::Remove { path } | Self::Trash { path } | Self::Unlink { path } => path,
+        }
+    }
+
+    fn execute(&self) -> Result<()> {
+        tracing::info!("{self}");
+
+        match self {
+            Self::Move { from, to } => move_dir(from, to),
+            Self::Copy { from, to } => copy_dir_all(from, to).inspect_err(|_| {
+                let _ = fs::remove_dir_all(to);
+            }),
+            Self::Remove { path } => Ok(fs::remove_dir_all(path)?),
+            Self::Trash { path } => {
+                if let Err(e) = trash::delete(path) {
+                    tracing::warn!("no trash for `{}`, deleting it: {e}", path.display());
+                    fs::remove_dir_all(path)?;
+                }
+                Ok(())
+            }
+            Self::Link { from, to } => link_dir(from, to),
+            Self::Unlink { path } => unlink_dir(path),
+        }
+    }
+}
+
+impl std::fmt::Display for PlannedOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Move { from, to } => {
+                write!(f, "moving `{}` to `{}`", from.display(), to.display())
+            }
+            Self::Copy { from, to } => {
+                write!(f, "copying `{}` to `{}`", from.display(), to.display())
+            }
+            Self::Remove { path } => write!(f, "removing `{}`", path.display()),
+            Self::Trash { path } => write!(f, "moving `{}` to the trash", path.display()),
+            Self::Link { from, to } => {
+                write!(f, "linking `{}` to `{}`", to.display(), from.display())
+            }
+            Self::Unlink { path } => write!(f, "removing the link `{}`", path.display()),
+        }
+    }
+}
+
+fn log_dry_run<'a>(ops: impl IntoIterator<Item = &'a PlannedOp>) {
+    for op in ops {
+        tracing::info!("dry run: {op}");
+    }
+}
+
+// favorites are stored like this so renaming a folder's casing or spacing doesn't lose them
+fn favorite_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+// whatever the casing, windows would see it as the same folder as `huds`
+fn is_reserved_name(name: &str) -> bool {
+    favorite_key(name) == HUDS
+}
+
+// `custom/huds`, unless a hud has been put there as if it were one, the manager's files don't
+// belong in a hud
+fn storage_dir(custom_dir: &Path) -> Result<PathBuf, HudError> {
+    let huds_dir = custom_dir.join(HUDS);
+    if huds_dir.join(INFO_VDF).is_file() {
+        return Err(HudError::ReservedName(HUDS.to_string()));
+    }
+
+    Ok(huds_dir)
+}
+
+// has to work as a folder name on windows too, whatever platform the hud is renamed on
+fn validate_folder_name(name: &str) -> Result<(), HudError> {
+    const RESERVED: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+    if name.trim().is_empty()
+        || name.trim_end_matches(['.', ' ']) != name
+        || name.contains(|c: char| RESERVED.contains(&c) || c.is_control())
+    {
+        return Err(HudError::InvalidName(name.to_string()));
+    }
+    if is_reserved_name(name) {
+        return Err(HudError::ReservedName(name.to_string()));
+    }
+
+    Ok(())
+}
+
+fn find_hud<'a>(huds: &'a mut [Hud], path: &Path) -> Result<&'a mut Hud> {
+    huds.iter_mut()
+        .find(|h| h.path == path)
+        .ok_or_else(|| HudError::HudNotFound(path.display().to_string()).into())
+}
+
+// github archives come as `repo-main/`, the hud is still just called `repo`
+fn strip_branch_suffix(name: &str) -> &str {
+    BRANCH_SUFFIXES
+        .iter()
+        .find_map(|s| name.strip_suffix(s))
+        .filter(|n| !n.is_empty())
+        .unwrap_or(name)
+}
+
+// always `/` separated, which every platform understands
+fn portable_path(path: &Path) -> PathBuf {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+        .into()
+}
+
+fn read_json<T: serde::de::DeserializeOwned + Default>(huds_dir: &Path, file: &str) -> Result<T> {
+    let path = huds_dir.join(file);
+
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let json = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read `{}`", path.display()))?;
+
+    serde_json::from_str(&json).with_context(|| format!("`{}` is invalid", path.display()))
+}
+
+fn write_json(huds_dir: &Path, file: &str, value: &impl serde::Serialize) -> Result<()> {
+    if !huds_dir.exists() {
+        fs::create_dir_all(huds_dir)?;
+    }
+
+    let path = huds_dir.join(file);
+    tracing::debug!("writing `{}`", path.display());
+
+    fs::write(&path, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("failed to write `{}`", path.display()))
+}
+
+// renames can't cross drives, which happens when `custom` is a symlink to another one
+fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            if let Err(e) = copy_dir_all(from, to) {
+                let _ = fs::remove_dir_all(to);
+                return Err(e);
+            }
+            fs::remove_dir_all(from)?;
+            Ok(())
+        }
+        moved => Ok(moved?),
+    }
+}
+
+fn is_link(path: &Path) -> bool {
+    path.symlink_metadata()
+        .is_ok_and(|m| m.file_type().is_symlink())
+}
+
+#[cfg(windows)]
+fn link_dir(from: &Path, to: &Path) -> Result<()> {
+    let Err(e) = std::os::windows::fs::symlink_dir(from, to) else {
+        return Ok(());
+    };
+
+    // symlinks need admin or developer mode, junctions don't
+    tracing::warn!(
+        "failed to symlink `{}`, trying a junction: {e}",
+        to.display()
+    );
+    let output = std::process::Command::new("cmd")
+        .args(["/C", "mklink", "/J"])
+        .args([to, from])
+        .output()
+        .with_context(|| "failed to run `mklink`")?;
+    ensure!(
+        output.status.success(),
+        "`mklink` failed: {}",
+        String::from_utf8_lossy(&output.stdout).trim()
+    );
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn link_dir(from: &Path, to: &Path) -> Result<()> {
+    Ok(std::os::unix::fs::symlink(from, to)?)
+}
+
+// only the link goes, never what it points at
+fn unlink_dir(path: &Path) -> Result<()> {
+    ensure!(is_link(path), "`{}` isn't a link", path.display());
+
+    // a link to a folder is a folder itself on windows
+    if cfg!(windows) {
+        fs::remove_dir(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+fn copy_dir_all(from: &Path, to: &Path) -> Result<()> {
+    for entry in WalkDir::new(from) {
+        let entry = entry?;
+        let to = to.join(entry.path().strip_prefix(from)?);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&to)?;
+        } else {
+            fs::copy(entry.path(), &to)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_info_vdf(entry: &walkdir::DirEntry) -> bool {
+    entry.path().ends_with(INFO_VDF)
+}
+
+fn find_thumbnail(hud: &Path) -> Option<PathBuf> {
+    let is_preview = |p: &Path| {
+        let matches = |s: Option<&std::ffi::OsStr>, names: &[&str]| {
+            s.and_then(|s| s.to_str())
+                .is_some_and(|s| names.iter().any(|n| s.eq_ignore_ascii_case(n)))
+        };
+
+        matches(p.file_stem(), PREVIEW_NAMES) && matches(p.extension(), PREVIEW_EXTENSIONS)
+    };
+
+    PREVIEW_DIRS
+        .iter()
+        .flat_map(|d| fs::read_dir(hud.join(d)).into_iter().flatten().flatten())
+        .map(|e| e.path())
+        .find(|p| p.is_file() && is_preview(p))
+        .and_then(|p| p.strip_prefix(hud).ok().map(Path::to_path_buf))
+}
+
+fn mtimes(vdf: &Path) -> Mtimes {
+    let modified = |p: &Path| fs::metadata(p).and_then(|m| m.modified()).ok();
+
+    Some((modified(vdf.parent()?)?, modified(vdf)?))
+}
+
+// returns the folder the `info.vdf` ended up in
+fn extract_hud<R: Read + Seek>(
+    zip: &mut zip::ZipArchive<R>,
+    source: &str,
+    tmp: &Path,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<PathBuf> {
+    let total = zip.len();
+    for i in 0..total {
+        let mut file = zip
+            .by_index(i)
+            .with_context(|| format!("failed to extract `{source}`"))?;
+        let Some(relative) = file.enclosed_name() else {
+            continue;
+        };
+
+        let to = tmp.join(relative);
+        if file.is_dir() {
+            fs::create_dir_all(&to)?;
+        } else {
+            fs::create_dir_all(to.parent().unwrap())?;
+            std::io::copy(&mut file, &mut fs::File::create(&to)?)
+                .with_context(|| format!("failed to extract `{source}`"))?;
+        }
+
+        progress(i + 1, total);
+    }
+
+    // the shallowest one is the hud, any deeper are samples or copies bundled inside it
+    let mut root = WalkDir::new(tmp)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .flatten()
+        .filter(|e| e.path().ends_with(INFO_VDF))
+        .min_by_key(walkdir::DirEntry::depth)
+        .with_context(|| format!("`{source}` doesn't contain an `{INFO_VDF}`"))?
+        .into_path();
+    root.pop();
+
+    Ok(root)
+}
+
+// a hud in `custom` or `huds/.disabled` counts as installed too, it's just active or disabled
+fn place_hud(
+    root: &Path,
+    custom_dir: &Path,
+    mut name: String,
+    existing: ExistingHud,
+) -> Result<String> {
+    let huds_dir = custom_dir.join(HUDS);
+    let disabled_dir = huds_dir.join(DISABLED);
+    let taken = |name: &str| {
+        huds_dir.join(name).exists()
+            || disabled_dir.join(name).exists()
+            || custom_dir.join(name).exists()
+    };
+
+    if is_reserved_name(&name) {
+        return Err(HudError::ReservedName(name).into());
+    }
+
+    if taken(&name) {
+        match existing {
+            ExistingHud::Refuse => return Err(HudError::AlreadyInstalled(name).into()),
+            ExistingHud::KeepBoth => {
+                name = (2..)
+                    .map(|i| format!("{name}-{i}"))
+                    .find(|n| !taken(n))
+                    .unwrap();
+            }
+            ExistingHud::Overwrite => {
+                ensure!(
+                    !custom_dir.join(&name).exists(),
+                    "`{name}` is active, switch to another hud before overwriting it"
+                );
+                // a disabled hud is overwritten where it is, so it stays disabled
+                let to = match disabled_dir.join(&name) {
+                    to if to.exists() => to,
+                    _ => huds_dir.join(&name),
+                };
+                replace_dir(root, &to).with_context(|| format!("failed to overwrite `{name}`"))?;
+                return Ok(name);
+            }
+        }
+    }
+
+    fs::rename(root, huds_dir.join(&name)).with_context(|| "failed to move hud")?;
+
+    Ok(name)
+}
+
+// an extra library belongs to whoever keeps it, so its huds are only ever copied or linked from,
+// never moved, renamed or deleted in it
+fn ensure_not_in_library(hud: &Hud, action: &str) -> Result<()> {
+    if let Some(source) = &hud.source {
+        bail!(
+            "`{}` is in `{}`, only huds in `huds` can be {action}",
+            hud.name,
+            source.display()
+        );
+    }
+
+    Ok(())
+}
+
+// the old folder is only deleted once the new one is in place, so a failure leaves it as it was
+fn replace_dir(from: &Path, to: &Path) -> Result<()> {
+    let old = to.with_file_name(REPLACED_TMP);
+    if old.exists() {
+        fs::remove_dir_all(&old)?;
+    }
+
+    fs::rename(to, &old)?;
+    if let Err(e) = fs::rename(from, to) {
+        let _ = fs::rename(&old, to);
+        return Err(e.into());
     }
+
+    Ok(fs::remove_dir_all(&old)?)
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct Hud {
     pub name: String,
     pub path: PathBuf,
     pub favorite: bool,
+    pub favorite_rank: Option<usize>,
+    pub modified: Option<SystemTime>,
+    pub display_name: Option<String>,
+    pub ui_version: Option<u32>,
+    pub author: Option<String>,
+    pub tags: Vec<String>,
+    pub disabled: bool,
+    pub last_activated: Option<SystemTime>,
+    pub note: Option<String>,
+    pub is_git_repo: bool,
+    // relative to `path` so it follows the hud around
+    pub thumbnail: Option<PathBuf>,
+    // the extra library it's from, `None` for `huds`
+    pub source: Option<PathBuf>,
 }
 
 impl Hud {
     fn from_vdf(vdf: PathBuf) -> Self {
+        let info = fs::read_to_string(&vdf).map(|s| vdf::Vdf::parse(&s));
+
         let mut path = vdf;
         path.pop();
 
         let name = path.file_name().unwrap().to_string_lossy().to_string();
 
+        let (display_name, root) = match info.as_ref().map(|i| i.entries().first()) {
+            Ok(Some((display_name, root @ vdf::Vdf::Object(_)))) => (
+                Some(display_name.clone()).filter(|n| !n.is_empty()),
+                Some(root),
+            ),
+            _ => (None, None),
+        };
+        let get = |key| {
+            root.and_then(|r| r.get(key))
+                .and_then(vdf::Vdf::as_str)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+
         Self {
             name,
+            thumbnail: find_thumbnail(&path),
+            source: None,
             path,
             favorite: false,
+            favorite_rank: None,
+            modified: None,
+            ui_version: get("ui_version").and_then(|v| v.parse().ok()),
+            author: get("author").map(str::to_string),
+            display_name,
+            tags: Vec::new(),
+            disabled: false,
+            last_activated: None,
+            note: None,
+            is_git_repo: false,
+        }
+    }
+}
+
+impl Hud {
+    // what to show for the hud, operations still go by `name`
+    pub fn title(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn info_vdf(&self) -> Result<PathBuf> {
+        WalkDir::new(&self.path)
+            .max_depth(2)
+            .into_iter()
+            .flatten()
+            .find(is_info_vdf)
+            .map(walkdir::DirEntry::into_path)
+            .with_context(|| format!("`{}` has no `{INFO_VDF}`", self.name))
+    }
+
+    pub fn git_pull(&self) -> Result<PullOutcome> {
+        let context = || format!("failed to update `{}`", self.name);
+
+        // `--ff-only` would refuse as well, but only once the changes actually conflict
+        let changes = self
+            .git(&["status", "--porcelain", "--untracked-files=no"])
+            .with_context(context)?;
+        ensure!(
+            changes.is_empty(),
+            HudError::GitLocalChanges(self.name.clone())
+        );
+
+        let from = self
+            .git(&["rev-parse", "--short", "HEAD"])
+            .with_context(context)?;
+        self.git(&["pull", "--ff-only", "--quiet"])
+            .with_context(context)?;
+        let to = self
+            .git(&["rev-parse", "--short", "HEAD"])
+            .with_context(context)?;
+
+        Ok(match from == to {
+            true => PullOutcome::UpToDate,
+            false => PullOutcome::FastForwarded { from, to },
+        })
+    }
+
+    pub fn update_available(&self) -> Result<bool> {
+        self.git(&["fetch", "--quiet"])
+            .with_context(|| format!("failed to check `{}` for updates", self.name))?;
+        let behind = self.git(&["rev-list", "--count", "HEAD..@{upstream}"])?;
+
+        Ok(behind.parse::<u32>().is_ok_and(|b| b > 0))
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        ensure!(
+            self.path.join(".git").exists(),
+            "`{}` isn't a git repo",
+            self.name
+        );
+
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&self.path)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .with_context(|| "failed to run git, is it installed?")?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        ensure!(
+            output.status.success(),
+            "{}",
+            stderr.lines().next().unwrap_or_default()
+        );
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    // tf2 usually won't load a hud with any of these, activating it is still allowed
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut problems = Vec::new();
+
+        match fs::read_to_string(self.path.join(INFO_VDF)) {
+            Ok(info) if info.trim().is_empty() => problems.push(ValidationWarning::EmptyInfoVdf),
+            Ok(_) => {}
+            Err(_) => problems.push(ValidationWarning::MissingInfoVdf),
+        }
+        for dir in ["resource", "scripts"] {
+            if !self.path.join(dir).is_dir() {
+                problems.push(ValidationWarning::MissingFolder(dir));
+            }
+        }
+
+        problems
+    }
+
+    pub fn file_count(&self) -> usize {
+        WalkDir::new(&self.path)
+            .into_iter()
+            .flatten()
+            .filter(|e| e.file_type().is_file())
+            .count()
+    }
+
+    // where a hud keeps its screenshots, if it has any
+    pub fn screenshots_dir(&self) -> PathBuf {
+        ["screenshots", "materials/console"]
+            .iter()
+            .map(|d| self.path.join(d))
+            .find(|d| d.is_dir())
+            .unwrap_or_else(|| self.path.clone())
+    }
+
+    pub fn thumbnail_path(&self) -> Option<PathBuf> {
+        self.thumbnail
+            .as_ref()
+            .map(|t| self.path.join(t))
+            .filter(|p| p.is_file())
+    }
+
+    // decoded on demand, scanning never opens the images
+    pub fn load_thumbnail(&self, size: u32) -> Option<image::RgbaImage> {
+        let path = self.thumbnail_path()?;
+
+        match image::open(&path) {
+            Ok(image) => Some(image.thumbnail(size, size).to_rgba8()),
+            Err(e) => {
+                tracing::warn!("failed to load `{}`: {e}", path.display());
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationWarning {
+    MissingInfoVdf,
+    EmptyInfoVdf,
+    MissingFolder(&'static str),
+}
+
+impl std::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingInfoVdf => write!(f, "missing `{INFO_VDF}`"),
+            Self::EmptyInfoVdf => write!(f, "`{INFO_VDF}` is empty"),
+            Self::MissingFolder(dir) => write!(f, "missing `{dir}` folder"),
         }
     }
 }
@@ -148,11 +2381,18 @@ fn from_vdf(vdf: PathBuf) -> Self {
 impl Ord for Hud {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self.favorite, other.favorite) {
-            (true, true) => self.name.cmp(&other.name),
+            (true, true) => {
+                // newly favorited huds don't have a rank yet, they go after the ranked ones
+                let rank = |h: &Self| h.favorite_rank.unwrap_or(usize::MAX);
+                rank(self)
+                    .cmp(&rank(other))
+                    .then_with(|| self.name.cmp(&other.name))
+            }
             (true, false) => Ordering::Less,
             (false, true) => Ordering::Greater,
             (false, false) => self.name.cmp(&other.name),
         }
+        .then_with(|| self.path.cmp(&other.path))
     }
 }
 
@@ -162,16 +2402,86 @@ fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
     }
 }
 
-fn custom_dir() -> Result<PathBuf> {
-    let mut custom_dir =
-        std::env::current_exe().with_context(|| "failed to get current exe dir")?;
-    custom_dir.pop();
+// the rows of a list that are in `range`, each with its position in `rows` so it can be matched
+// against the selection, `in_list` picks which of `rows` the list shows
+pub fn rows_in_view<T>(
+    rows: impl Iterator<Item = T>,
+    in_list: impl Fn(&T) -> bool,
+    range: Range<usize>,
+) -> impl Iterator<Item = (usize, T)> {
+    rows.enumerate()
+        .filter(move |(_, row)| in_list(row))
+        .skip(range.start)
+        .take(range.len())
+}
+
+pub fn activate(hud: &str) -> Result<(), HudError> {
+    let mut huds = Huds::default();
+
+    huds.update_favorites()?;
+    huds.scan_for_huds()?;
+    huds.set_active_hud(hud)
+}
+
+pub fn custom_dir() -> Result<PathBuf, HudError> {
+    if let Some(custom_dir) = std::env::var_os(CUSTOM_DIR_VAR).filter(|d| !d.is_empty()) {
+        let custom_dir = PathBuf::from(custom_dir);
+        if !custom_dir.is_dir() {
+            return Err(HudError::CustomDirNotFound(format!(
+                "`{CUSTOM_DIR_VAR}` is set to `{}` which isn't a folder",
+                custom_dir.display()
+            )));
+        }
+        return Ok(custom_dir);
+    }
 
-    if custom_dir.ends_with("custom/huds") {
-        custom_dir.pop();
-    } else if !custom_dir.ends_with("custom") {
-        bail!("exe must be in `custom` or `custom\\huds`");
+    if let Some(custom_dir) = Config::load()?.custom_dir {
+        if !custom_dir.is_dir() {
+            return Err(HudError::CustomDirNotFound(format!(
+                "`custom_dir` in the config is set to `{}` which isn't a folder",
+                custom_dir.display()
+            )));
+        }
+        return Ok(custom_dir);
     }
 
-    Ok(custom_dir)
+    let exe = std::env::current_exe().with_context(|| "failed to get current exe dir")?;
+    let exe_dir = exe
+        .parent()
+        .with_context(|| "failed to get current exe dir")?;
+
+    find_custom_dir(exe_dir).ok_or_else(|| {
+        HudError::CustomDirNotFound("exe must be somewhere inside a `custom` folder".to_string())
+    })
+}
+
+fn looks_like_custom_dir(dir: &Path) -> bool {
+    dir.file_name().is_some_and(|n| n == "custom")
+        && dir
+            .parent()
+            .is_some_and(|tf| tf.join("gameinfo.txt").is_file())
+}
+
+// the closest `custom` above `start`, the game's own `tf/custom` wins over a folder that just
+// happens to be called `custom` further down, like one inside a hud
+pub fn find_custom_dir(start: &Path) -> Option<PathBuf> {
+    let is_custom = |d: &&Path| d.file_name().is_some_and(|n| n == "custom");
+    let in_tf = |d: &&Path| {
+        d.parent()
+            .and_then(Path::file_name)
+            .is_some_and(|n| n == "tf")
+    };
+
+    let mut candidates = start.ancestors().filter(is_custom);
+    let nearest = candidates.clone().next()?;
+
+    Some(candidates.find(in_tf).unwrap_or(nearest).to_path_buf())
+}
+
+pub fn custom_dir_with_fallback() -> Result<PathBuf, HudError> {
+    custom_dir().or_else(|e| {
+        steam::tf2_custom_dir().ok_or_else(|| {
+            HudError::CustomDirNotFound(format!("{e}, and tf2 couldn't be found through steam"))
+        })
+    })
 }
@@ -0,0 +1,98 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use tracing::level_filters::LevelFilter;
+
+use crate::config::Config;
+
+const LOG_FILE: &str = "hud_manager.log";
+const OLD_LOG_FILE: &str = "hud_manager.old.log";
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+// `error`, `warn`, `info`, `debug`, `trace` or `off`
+const LOG_LEVEL_VAR: &str = "HUD_MANAGER_LOG";
+
+// writes `hud_manager.log` in the config folder, moving it to `hud_manager.old.log` once it gets
+// too big so there's never more than two of them
+pub fn init_logging() -> Result<()> {
+    let dir = match Config::dir() {
+        Some(dir) => dir,
+        None => {
+            let exe = std::env::current_exe().with_context(|| "failed to get current exe dir")?;
+            exe.parent()
+                .with_context(|| "failed to get current exe dir")?
+                .to_path_buf()
+        }
+    };
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create `{}`", dir.display()))?;
+
+    let level = std::env::var(LOG_LEVEL_VAR).ok();
+    let parsed = level.as_deref().map(str::parse::<LevelFilter>);
+
+    let path = dir.join(LOG_FILE);
+    let file = open(&path)?;
+    let size = file.metadata().map_or(0, |m| m.len());
+
+    tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_max_level(match parsed {
+            Some(Ok(level)) => level,
+            _ => LevelFilter::INFO,
+        })
+        .with_writer(Mutex::new(LogFile { path, file, size }))
+        .try_init()
+        .map_err(|e| anyhow::anyhow!(e))
+        .with_context(|| "failed to start logging")?;
+
+    if let (Some(level), Some(Err(_))) = (&level, parsed) {
+        tracing::warn!("`{LOG_LEVEL_VAR}` isn't a log level: `{level}`, using `info`");
+    }
+
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<fs::File> {
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open `{}`", path.display()))
+}
+
+struct LogFile {
+    path: PathBuf,
+    file: fs::File,
+    size: u64,
+}
+
+impl LogFile {
+    fn rotate(&mut self) -> io::Result<()> {
+        fs::rename(&self.path, self.path.with_file_name(OLD_LOG_FILE))?;
+        self.file = open(&self.path).map_err(io::Error::other)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // if it can't be moved aside, start over in the same file so it still can't grow forever,
+        // it's opened for appending so writes carry on from the start
+        if self.size + buf.len() as u64 > MAX_LOG_SIZE && self.rotate().is_err() {
+            self.file.set_len(0)?;
+            self.size = 0;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
@@ -1,51 +1,423 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 
+use anyhow::Context as _;
 use eframe::egui::*;
-use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
-use nucleo_matcher::Matcher;
 
-use hud_manager::{Hud, Huds};
+use hud_manager::{
+    rows_in_view, ActivationStrategy, Config, ExistingHud, Hud, HudError, Huds, Profile,
+    PullOutcome, ScanProgress, SortMode, ValidationWarning, Watcher,
+};
 
 const FONT_NAME: &str = "Inter";
 const FONT_DATA: &[u8] = include_bytes!("../Inter-Regular.ttf");
+const WINDOW_SIZE: Vec2 = Vec2::new(650.0, 700.0);
+const PREVIEW_SIZE: u32 = 320;
+const RECENT_SEARCHES: usize = 10;
+const RECENT_HUDS: usize = 5;
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+const FLASH_DURATION: Duration = Duration::from_secs(1);
 
 enum Msg {
     Favorited,
-    SetActive(String),
+    SetActive(PathBuf),
+    MoveFavoriteUp(String),
+    MoveFavoriteDown(String),
+    MoveFavorite(String, usize),
+    GitUpdate(String),
+    Duplicate(String),
+    Rename(String),
+    AddTag(String, String),
+    RemoveTag(String, String),
+    Disable(String),
+    Enable(String),
+    Delete(String),
+    Deactivate,
+    TogglePair,
+    Undo,
+    Rescan,
     Error(anyhow::Error),
 }
 
+#[derive(Clone)]
+enum InstallSource {
+    Zip(PathBuf),
+    Github(String),
+}
+
+enum Scan {
+    Progress(ScanProgress),
+    Done(Box<Result<Huds, HudError>>),
+}
+
+enum Install {
+    Progress(usize, usize),
+    Done(Result<String, HudError>),
+}
+
+type UpdateCheck = Vec<(String, anyhow::Result<bool>)>;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Settings {
+    window_pos: Option<Pos2>,
+    window_size: Option<Vec2>,
+    search_threshold: f32,
+    sort_mode: SortMode,
+    confirm_switch: bool,
+    safe_mode: bool,
+    pin_active: bool,
+    reveal_active: bool,
+    profile: Option<String>,
+    dark_mode: bool,
+    favorites_only: bool,
+    active_only: bool,
+    recent_searches: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            window_pos: None,
+            window_size: None,
+            search_threshold: 0.8,
+            sort_mode: SortMode::default(),
+            confirm_switch: false,
+            safe_mode: false,
+            pin_active: false,
+            reveal_active: false,
+            profile: None,
+            dark_mode: true,
+            favorites_only: false,
+            active_only: false,
+            recent_searches: Vec::new(),
+        }
+    }
+}
+
+// things that are slow to work out for every hud, filled in the first time they're needed
+#[derive(Default)]
+struct HudCache {
+    previews: HashMap<String, Option<TextureHandle>>,
+    file_counts: HashMap<String, usize>,
+    problems: HashMap<String, Vec<ValidationWarning>>,
+}
+
 #[derive(Default)]
 struct App {
     huds: Huds,
+    scan: Option<Receiver<Scan>>,
+    scan_progress: Option<ScanProgress>,
+    install: Option<Receiver<Install>>,
+    install_source: Option<InstallSource>,
+    install_progress: Option<(usize, usize)>,
+    update_check: Option<Receiver<UpdateCheck>>,
+    updates: HashSet<String>,
+    github_url: String,
+    custom_dir: String,
+    favorites_file: String,
+    watcher: Option<Watcher>,
+    profiles: Vec<Profile>,
+    settings: Settings,
+    // the saved window geometry, kept aside until it's put back since it's saved over every frame
+    restore_pos: Option<Pos2>,
+    restore_size: Option<Vec2>,
+    title: String,
+    reveal_active: bool,
+    flash_active_until: Option<Instant>,
 
     search: String,
     search_results: HashSet<String>,
-    matcher: Matcher,
+    tag_filter: HashSet<String>,
+    new_tag: String,
+    selected: Option<usize>,
+    scroll_to_selected: bool,
+    focus_search: bool,
+    cache: HudCache,
 
+    confirm_switch: Option<PathBuf>,
+    confirm_git_update: Option<String>,
+    confirm_delete: Option<String>,
+    note: Option<(String, String)>,
+    duplicate: Option<(String, String)>,
+    rename: Option<(String, String)>,
+    install_conflict: Option<(InstallSource, String)>,
     msg: Option<Msg>,
     error: String,
+    toasts: Vec<(String, Instant)>,
 }
 
 impl App {
-    fn new() -> Self {
-        let mut huds = Huds::default();
+    fn new(cc: &eframe::CreationContext) -> Self {
+        let settings: Settings = cc
+            .storage
+            .and_then(|s| eframe::get_value(s, eframe::APP_KEY))
+            .unwrap_or_default();
 
-        let error = huds
-            .update_favorites()
-            .and_then(|_| huds.scan_for_huds())
-            .map_or_else(|e| format!("{e:#}"), |_| String::new());
+        cc.egui_ctx.set_visuals(visuals(settings.dark_mode));
 
-        Self {
-            huds,
-            error,
+        let mut app = Self {
+            restore_pos: settings.window_pos.filter(|p| p.is_finite()),
+            restore_size: settings
+                .window_size
+                .filter(|s| s.is_finite() && s.min_elem() > 0.0),
+            settings,
             ..Default::default()
+        };
+
+        match Config::load() {
+            Ok(config) => {
+                app.profiles = config.profiles;
+                app.custom_dir = config
+                    .custom_dir
+                    .map(|d| d.display().to_string())
+                    .unwrap_or_default();
+            }
+            Err(e) => app.error(e),
+        }
+
+        app.reveal_active = app.settings.reveal_active;
+        app.start_scan(&cc.egui_ctx);
+        app
+    }
+
+    fn start_scan(&mut self, ctx: &Context) {
+        let profile = self
+            .profiles
+            .iter()
+            .find(|p| Some(&p.name) == self.settings.profile.as_ref())
+            .cloned();
+        let dry_run = self.settings.safe_mode;
+
+        let (tx, scan) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let mut huds = Huds::default();
+            huds.profile = profile;
+            huds.dry_run = dry_run;
+            let progress = |progress| {
+                let _ = tx.send(Scan::Progress(progress));
+                ctx.request_repaint();
+            };
+            let scanned = huds
+                .update_favorites()
+                .and_then(|_| huds.scan_for_huds_cached_with_progress(progress))
+                .map(|_| huds);
+
+            let _ = tx.send(Scan::Done(Box::new(scanned)));
+            ctx.request_repaint();
+        });
+
+        self.scan = Some(scan);
+        self.scan_progress = None;
+        self.watcher = None;
+        self.selected = None;
+    }
+
+    fn poll_scan(&mut self, ctx: &Context) {
+        let Some(scan) = &self.scan else {
+            return;
+        };
+
+        let scanned = loop {
+            match scan.try_recv() {
+                Ok(Scan::Progress(progress)) => self.scan_progress = Some(progress),
+                Ok(Scan::Done(scanned)) => break *scanned,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    break Err(HudError::Other(anyhow::anyhow!(
+                        "scanning stopped unexpectedly"
+                    )))
+                }
+            }
+        };
+        self.scan = None;
+        self.scan_progress = None;
+
+        let repaint = ctx.clone();
+        let watcher = scanned.and_then(|huds| {
+            self.huds = huds;
+            self.huds.sort_by(self.settings.sort_mode);
+            self.huds
+                .watch(move |after| repaint.request_repaint_after(after))
+        });
+
+        let scanned = watcher.map(|watcher| self.watcher = Some(watcher));
+        self.scanned(scanned);
+        self.check_for_updates(ctx);
+    }
+
+    fn check_for_updates(&mut self, ctx: &Context) {
+        let git_huds = self
+            .huds
+            .huds
+            .iter()
+            .filter(|h| h.is_git_repo)
+            .cloned()
+            .collect::<Vec<_>>();
+        if git_huds.is_empty() {
+            return;
+        }
+
+        let (tx, update_check) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let updates = git_huds
+                .into_iter()
+                .map(|h| {
+                    let update_available = h.update_available();
+                    (h.name, update_available)
+                })
+                .collect();
+
+            let _ = tx.send(updates);
+            ctx.request_repaint();
+        });
+
+        self.update_check = Some(update_check);
+    }
+
+    fn poll_update_check(&mut self) {
+        let Some(update_check) = &self.update_check else {
+            return;
+        };
+
+        let Ok(updates) = update_check.try_recv() else {
+            return;
+        };
+        self.update_check = None;
+
+        self.updates.clear();
+        let mut error = None;
+        for (hud, update_available) in updates {
+            match update_available {
+                Ok(true) => {
+                    self.updates.insert(hud);
+                }
+                Ok(false) => {}
+                Err(e) => error = error.or(Some(e)),
+            }
+        }
+
+        if let Some(e) = error {
+            self.error(e);
+        }
+    }
+
+    fn install(&mut self, ctx: &Context, source: InstallSource, existing: ExistingHud) {
+        if self.install.is_some() {
+            self.error(anyhow::anyhow!("another hud is still being installed"));
+            return;
+        }
+
+        let profile = self.huds.profile.clone();
+        self.install_source = Some(source.clone());
+
+        let (tx, install) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let mut huds = Huds::default();
+            huds.profile = profile;
+
+            let progress = |done, total| {
+                let _ = tx.send(Install::Progress(done, total));
+                ctx.request_repaint();
+            };
+            let installed = match &source {
+                InstallSource::Zip(archive) => huds.install_from_zip(archive, existing, progress),
+                InstallSource::Github(url) => huds.install_from_github(url, existing, progress),
+            };
+
+            let _ = tx.send(Install::Done(installed));
+            ctx.request_repaint();
+        });
+
+        self.install = Some(install);
+        self.install_progress = None;
+    }
+
+    fn poll_install(&mut self) {
+        let Some(install) = &self.install else {
+            return;
+        };
+
+        let installed = loop {
+            match install.try_recv() {
+                Ok(Install::Progress(done, total)) => self.install_progress = Some((done, total)),
+                Ok(Install::Done(installed)) => break installed,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    break Err(HudError::Other(anyhow::anyhow!(
+                        "installing stopped unexpectedly"
+                    )))
+                }
+            }
+        };
+        self.install = None;
+        self.install_progress = None;
+        let source = self.install_source.take();
+
+        match installed.and_then(|hud| self.huds.scan_incremental().map(|_| hud)) {
+            Ok(hud) => self.error = format!("installed `{hud}`"),
+            Err(HudError::AlreadyInstalled(hud)) if source.is_some() => {
+                self.install_conflict = source.map(|source| (source, hud));
+            }
+            Err(e) => self.error(e),
+        }
+    }
+
+    // the window doesn't know its monitor until it's up, which can take a few frames, so this is
+    // tried every frame until it does
+    fn restore_window(&mut self, ctx: &Context) {
+        let (monitor_size, outer_rect) =
+            ctx.input(|i| (i.viewport().monitor_size, i.viewport().outer_rect));
+        let Some(monitor_size) = monitor_size else {
+            return;
+        };
+
+        let size = self.restore_size.take().map(|s| s.min(monitor_size));
+        if let Some(size) = size {
+            ctx.send_viewport_cmd(ViewportCommand::InnerSize(size));
+        }
+
+        // the window opens in the middle of its monitor, which is the only way to tell where that
+        // monitor is. one that's been unplugged or shrunk would leave the window out of reach, so
+        // pull it back until all of it fits on this one. without a position of its own, like on
+        // wayland, the window can't be placed at all
+        if let (Some(pos), Some(outer_rect)) = (self.restore_pos.take(), outer_rect) {
+            let monitor = Rect::from_center_size(outer_rect.center(), monitor_size);
+            let max = (monitor.max - size.unwrap_or(WINDOW_SIZE)).max(monitor.min);
+            let pos = pos.clamp(monitor.min, max);
+            ctx.send_viewport_cmd(ViewportCommand::OuterPosition(pos));
+        }
+    }
+
+    fn update_title(&mut self, ctx: &Context) {
+        let active = self
+            .huds
+            .active_hud
+            .as_ref()
+            .map_or("none", |h| h.name.as_str());
+        let title = format!(
+            "hud manager — {} huds — active: {active}",
+            self.huds.huds.len()
+        );
+
+        if title != self.title {
+            ctx.send_viewport_cmd(ViewportCommand::Title(title.clone()));
+            self.title = title;
         }
     }
 
     fn search(&mut self) {
+        self.keep_selection(Self::update_search_results);
+    }
+
+    fn update_search_results(&mut self) {
         self.search_results.clear();
         self.error.clear();
 
@@ -53,56 +425,694 @@ fn search(&mut self) {
             return;
         }
 
-        let hud_names = self.huds.huds.iter().map(|h| h.name.as_str());
-        let search_results =
-            Pattern::parse(&self.search, CaseMatching::Ignore, Normalization::Never)
-                .match_list(hud_names, &mut self.matcher);
+        let search_results = self
+            .huds
+            .search(&self.search, self.settings.search_threshold)
+            .into_iter()
+            .filter(|hud| {
+                is_shown(
+                    &HashSet::new(),
+                    &HashSet::new(),
+                    &self.settings,
+                    self.huds.active_hud.as_ref(),
+                    hud,
+                )
+            })
+            .map(|hud| hud.name.clone())
+            .collect::<HashSet<_>>();
+
+        // an empty `search_results` shows every hud, which is what a search with no matches gets
+        if search_results.is_empty() {
+            self.error = "no matches, showing all".to_string();
+            return;
+        }
+        self.search_results = search_results;
+
+        let recent = &mut self.settings.recent_searches;
+        recent.retain(|s| *s != self.search);
+        recent.insert(0, self.search.clone());
+        recent.truncate(RECENT_SEARCHES);
+    }
+
+    fn clear_search(&mut self) {
+        self.keep_selection(|app| {
+            app.search.clear();
+            app.search_results.clear();
+            app.error.clear();
+        });
+    }
+
+    fn shown_huds(&self) -> impl Iterator<Item = &Hud> {
+        self.huds.huds.iter().filter(|hud| {
+            is_shown(
+                &self.search_results,
+                &self.tag_filter,
+                &self.settings,
+                self.huds.active_hud.as_ref(),
+                hud,
+            )
+        })
+    }
+
+    // `selected` is a position in the shown list, so it has to be looked up again whenever the
+    // list is filtered or sorted to stay on the same hud
+    fn keep_selection(&mut self, change: impl FnOnce(&mut Self)) {
+        let selected = self
+            .selected
+            .and_then(|i| self.shown_huds().nth(i))
+            .map(|h| h.path.clone());
+
+        change(self);
+
+        self.selected = selected.and_then(|p| self.shown_huds().position(|h| h.path == p));
+        self.scroll_to_selected = self.selected.is_some();
+    }
+
+    fn keyboard_navigation(&mut self, ctx: &Context) {
+        let shown = |hud: &&mut Hud| {
+            is_shown(
+                &self.search_results,
+                &self.tag_filter,
+                &self.settings,
+                self.huds.active_hud.as_ref(),
+                hud,
+            )
+        };
+        let total_rows = self.huds.huds.iter_mut().filter(shown).count();
+
+        let (up, down) =
+            ctx.input(|i| (i.key_pressed(Key::ArrowUp), i.key_pressed(Key::ArrowDown)));
+        if up || down {
+            if let Some(id) = ctx.memory(|m| m.focus()) {
+                ctx.memory_mut(|m| m.surrender_focus(id));
+            }
+        }
+
+        let last = total_rows.checked_sub(1);
+        self.selected = match (self.selected, up, down) {
+            (Some(i), true, false) => Some(i.saturating_sub(1)),
+            (Some(i), false, true) => Some(i + 1),
+            (None, true, false) => last,
+            (None, false, true) => Some(0),
+            (selected, ..) => selected,
+        }
+        .zip(last)
+        .map(|(i, last)| i.min(last));
+        self.scroll_to_selected |= up || down;
+
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        // there's no key for `/` in egui, the typed text is all there is
+        if ctx.input(|i| i.events.contains(&Event::Text("/".to_string()))) {
+            self.focus_search = true;
+            return;
+        }
+
+        let Some(hud) = self
+            .selected
+            .and_then(|i| self.huds.huds.iter_mut().filter(shown).nth(i))
+        else {
+            return;
+        };
+
+        if ctx.input(|i| i.key_pressed(Key::Enter)) {
+            self.msg = Some(Msg::SetActive(hud.path.clone()));
+        } else if ctx.input(|i| i.key_pressed(Key::Space) || i.key_pressed(Key::F)) {
+            hud.favorite = !hud.favorite;
+            self.msg = Some(Msg::Favorited);
+        }
+    }
+
+    fn set_active(&mut self, path: &Path) {
+        let scanned = self
+            .huds
+            .set_active_by_path(path)
+            .and_then(|_| self.huds.scan_incremental());
+        self.scanned(scanned);
+
+        if self.huds.dry_run && self.error.is_empty() {
+            self.error = "safe mode is on, the moves were only logged".to_string();
+        }
+    }
+
+    fn scanned(&mut self, scanned: Result<(), HudError>) {
+        self.cache.file_counts.clear();
+        self.cache.problems.clear();
+
+        if let Err(e) = scanned {
+            self.error(e);
+            return;
+        }
+
+        let duplicates = self.huds.duplicates();
+        if !duplicates.is_empty() {
+            self.error = format!("multiple huds share a name: {}", duplicates.join(", "));
+        }
+
+        match self.huds.detect_strays() {
+            Ok(strays) if !strays.is_empty() => {
+                let names = strays
+                    .iter()
+                    .map(|p| p.file_name().unwrap_or_default().to_string_lossy())
+                    .collect::<Vec<_>>();
+                self.error = format!(
+                    "unmanaged content detected in `custom`: {}",
+                    names.join(", ")
+                );
+            }
+            Ok(_) => {}
+            Err(e) => self.error(e),
+        }
+    }
+
+    fn confirm_switch_window(&mut self, ctx: &Context) {
+        let Some(path) = self.confirm_switch.clone() else {
+            return;
+        };
+        let hud = self
+            .huds
+            .huds
+            .iter()
+            .find(|h| h.path == path)
+            .map_or_else(|| path.display().to_string(), |h| h.name.clone());
+
+        let current = self
+            .huds
+            .active_hud
+            .as_ref()
+            .map_or("none", |h| h.name.as_str());
+        let plan = self.huds.plan_set_active_by_path(&path);
+        let mut confirmed = None;
+
+        Window::new("Switch hud?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("Switch to `{hud}`?"));
+                ui.label(format!("Current hud: `{current}`"));
+                match &plan {
+                    Ok(plan) => {
+                        for op in plan {
+                            ui.small(op.to_string());
+                        }
+                    }
+                    Err(e) => {
+                        ui.colored_label(Color32::RED, e.to_string());
+                    }
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("OK").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) => {
+                self.confirm_switch = None;
+                self.set_active(&path);
+            }
+            Some(false) => self.confirm_switch = None,
+            None => {}
+        }
+    }
+
+    fn install_conflict_window(&mut self, ctx: &Context) {
+        let Some((source, hud)) = self.install_conflict.clone() else {
+            return;
+        };
+
+        let mut existing = None;
+        let mut cancelled = false;
+
+        Window::new("Hud already installed")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("`{hud}` is already installed."));
+                ui.horizontal(|ui| {
+                    if ui.button("Overwrite").clicked() {
+                        existing = Some(ExistingHud::Overwrite);
+                    }
+                    if ui
+                        .button("Keep both")
+                        .on_hover_text(format!("install as `{hud}-2`"))
+                        .clicked()
+                    {
+                        existing = Some(ExistingHud::KeepBoth);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        if let Some(existing) = existing {
+            self.install_conflict = None;
+            self.install(ctx, source, existing);
+        } else if cancelled {
+            self.install_conflict = None;
+        }
+    }
+
+    fn confirm_delete_window(&mut self, ctx: &Context) {
+        let Some(hud) = self.confirm_delete.clone() else {
+            return;
+        };
+
+        let mut confirmed = None;
+
+        Window::new("Delete hud?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("Delete `{hud}`?"));
+                ui.label("It goes to the trash if there is one.");
+                ui.horizontal(|ui| {
+                    if ui.button("Delete").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) => {
+                self.confirm_delete = None;
+                match self.huds.delete_hud(&hud) {
+                    Ok(()) if self.huds.dry_run => {
+                        self.error = "safe mode is on, the delete was only logged".to_string()
+                    }
+                    Ok(()) => self.error = format!("deleted `{hud}`"),
+                    Err(e) => self.error(e),
+                }
+            }
+            Some(false) => self.confirm_delete = None,
+            None => {}
+        }
+    }
+
+    fn confirm_git_update_window(&mut self, ctx: &Context) {
+        let Some(hud) = self.confirm_git_update.clone() else {
+            return;
+        };
+
+        let mut confirmed = None;
 
-        let highest_score = if search_results.is_empty() {
-            self.error(anyhow::anyhow!("no results"));
+        Window::new("Update active hud?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "`{hud}` is active, updating it may change files while the game is using them."
+                ));
+                ui.horizontal(|ui| {
+                    if ui.button("Update anyway").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) => {
+                self.confirm_git_update = None;
+                if let Some(active_hud) = self.huds.active_hud.clone() {
+                    let updated = self.huds.update_hud(&active_hud.name, true);
+                    self.git_updated(&hud, updated);
+                }
+            }
+            Some(false) => self.confirm_git_update = None,
+            None => {}
+        }
+    }
+
+    fn git_updated(&mut self, hud: &str, updated: Result<PullOutcome, HudError>) {
+        let output = match updated.and_then(|o| self.huds.scan_incremental().map(|_| o)) {
+            Ok(output) => output,
+            Err(e) => {
+                self.error(e);
+                return;
+            }
+        };
+
+        self.updates.remove(hud);
+        self.error = format!("`{hud}`: {output}");
+    }
+
+    fn duplicate_window(&mut self, ctx: &Context) {
+        let Some((hud, new_name)) = &mut self.duplicate else {
+            return;
+        };
+
+        let mut confirmed = None;
+
+        Window::new(format!("Duplicate `{hud}`"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add(TextEdit::singleline(new_name).hint_text("new name"));
+                ui.horizontal(|ui| {
+                    if ui.button("OK").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) => {
+                let (hud, new_name) = self.duplicate.take().unwrap();
+                match self.huds.duplicate_hud(&hud, new_name.trim()) {
+                    Ok(()) => self.error = format!("duplicated `{hud}` as `{}`", new_name.trim()),
+                    Err(e) => self.error(e),
+                }
+            }
+            Some(false) => self.duplicate = None,
+            None => {}
+        }
+    }
+
+    fn rename_window(&mut self, ctx: &Context) {
+        let Some((hud, new_name)) = &mut self.rename else {
             return;
-        } else {
-            search_results[0].1
         };
 
-        for (hud, score) in search_results {
-            if (score as f32 / highest_score as f32) >= 0.8 {
-                self.search_results.insert(hud.to_string());
+        let mut confirmed = None;
+
+        Window::new(format!("Rename `{hud}`"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add(TextEdit::singleline(new_name).hint_text("new name"));
+                ui.horizontal(|ui| {
+                    if ui.button("OK").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+
+        match confirmed {
+            Some(true) => {
+                let (hud, new_name) = self.rename.take().unwrap();
+                match self.huds.rename_hud(&hud, new_name.trim()) {
+                    Ok(()) if self.huds.dry_run => {
+                        self.error = "safe mode is on, the rename was only logged".to_string()
+                    }
+                    Ok(()) => self.error = format!("renamed `{hud}` to `{}`", new_name.trim()),
+                    Err(e) => self.error(e),
+                }
+            }
+            Some(false) => self.rename = None,
+            None => {}
+        }
+    }
+
+    fn note_window(&mut self, ctx: &Context) {
+        let Some((hud, note)) = &mut self.note else {
+            return;
+        };
+
+        let mut done = None;
+
+        Window::new(format!("Notes for `{hud}`"))
+            .collapsible(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.add(TextEdit::multiline(note).desired_width(f32::INFINITY));
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        done = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        done = Some(false);
+                    }
+                });
+            });
+
+        match done {
+            Some(true) => {
+                let (hud, note) = self.note.take().unwrap();
+                if let Err(e) = self.huds.set_note(&hud, &note) {
+                    self.error(e);
+                }
+            }
+            Some(false) => self.note = None,
+            None => {}
+        }
+    }
+
+    fn install_zip(&mut self, ctx: &Context, archive: PathBuf) {
+        let is_zip = archive
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("zip"));
+
+        if !is_zip {
+            self.error(anyhow::anyhow!("`{}` is not a zip", archive.display()));
+            return;
+        }
+
+        self.install(ctx, InstallSource::Zip(archive), ExistingHud::Refuse);
+    }
+
+    fn import_favorites(&mut self, path: &Path, merge: bool) {
+        match self.huds.import_favorites(path, merge) {
+            Ok(missing) if missing.is_empty() => {
+                self.error = format!("imported favorites from `{}`", path.display())
             }
+            Ok(missing) => {
+                self.error = format!(
+                    "imported favorites from `{}`, these aren't installed: {}",
+                    path.display(),
+                    missing.join(", ")
+                )
+            }
+            Err(e) => self.error(e),
         }
     }
 
-    fn error(&mut self, e: anyhow::Error) {
-        self.error = format!("{e:#}");
+    fn export_favorites(&mut self, path: &Path) {
+        match self.huds.export_favorites(path) {
+            Ok(()) => self.error = format!("exported favorites to `{}`", path.display()),
+            Err(e) => self.error(e),
+        }
+    }
+
+    fn toasts(&mut self, ctx: &Context) {
+        self.toasts
+            .retain(|(_, shown)| shown.elapsed() < TOAST_DURATION);
+
+        let mut dismissed = None;
+        Area::new("toasts")
+            .anchor(Align2::RIGHT_TOP, [-8.0, 8.0])
+            .order(Order::Foreground)
+            .show(ctx, |ui| {
+                for (i, (toast, _)) in self.toasts.iter().enumerate() {
+                    let response = Frame::popup(ui.style())
+                        .show(ui, |ui| ui.label(toast))
+                        .response
+                        .interact(Sense::click())
+                        .on_hover_text("click to dismiss");
+                    if response.clicked() {
+                        dismissed = Some(i);
+                    }
+                }
+            });
+
+        if let Some(i) = dismissed {
+            self.toasts.remove(i);
+        }
+        if let Some((_, shown)) = self.toasts.first() {
+            ctx.request_repaint_after(TOAST_DURATION.saturating_sub(shown.elapsed()));
+        }
+    }
+
+    fn error(&mut self, e: impl Into<anyhow::Error>) {
+        let e = e.into();
+        tracing::error!("{e:#}");
+        self.toasts.push((format!("{e:#}"), Instant::now()));
     }
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        if self.restore_pos.is_some() || self.restore_size.is_some() {
+            self.restore_window(ctx);
+        }
+
+        ctx.input(|i| {
+            let viewport = i.viewport();
+            if let Some(rect) = viewport.outer_rect {
+                self.settings.window_pos = Some(rect.min);
+            }
+            if let Some(rect) = viewport.inner_rect {
+                self.settings.window_size = Some(rect.size());
+            }
+        });
+
+        let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+        if ctx.memory(|m| m.focus().is_none()) && ctx.input_mut(|i| i.consume_shortcut(&undo)) {
+            self.msg = Some(Msg::Undo);
+        }
+
+        if ctx.input(|i| i.key_pressed(Key::F8)) {
+            self.msg = Some(Msg::TogglePair);
+        }
+
+        self.poll_scan(ctx);
+        self.poll_install();
+        self.poll_update_check();
+
+        if self.watcher.as_mut().is_some_and(|w| w.changed()) && self.msg.is_none() {
+            self.msg = Some(Msg::Rescan);
+        }
+
+        self.keyboard_navigation(ctx);
+
         if let Some(msg) = self.msg.take() {
             self.error.clear();
 
             match msg {
                 Msg::Favorited => {
-                    self.huds.huds.sort_unstable();
+                    self.keep_selection(|app| app.huds.sort());
 
                     if let Err(e) = self.huds.save_favorites() {
                         self.error(e);
                     }
                 }
-                Msg::SetActive(hud) => {
-                    if let Err(e) = self
-                        .huds
-                        .set_active_hud(&hud)
-                        .and_then(|_| self.huds.scan_for_huds())
-                    {
+                Msg::SetActive(path) => {
+                    let changes_hud = self.huds.active_hud.as_ref().is_none_or(|a| a.path != path);
+
+                    if self.settings.confirm_switch && changes_hud {
+                        self.confirm_switch = Some(path);
+                    } else {
+                        self.set_active(&path);
+                    }
+                }
+                Msg::MoveFavoriteUp(hud) => {
+                    if let Err(e) = self.huds.move_favorite_up(&hud) {
+                        self.error(e);
+                    }
+                }
+                Msg::MoveFavoriteDown(hud) => {
+                    if let Err(e) = self.huds.move_favorite_down(&hud) {
+                        self.error(e);
+                    }
+                }
+                Msg::MoveFavorite(hud, index) => {
+                    if let Err(e) = self.huds.move_favorite(&hud, index) {
+                        self.error(e);
+                    }
+                }
+                Msg::GitUpdate(hud) => {
+                    if self.huds.active_hud.as_ref().is_some_and(|a| a.name == hud) {
+                        self.confirm_git_update = Some(hud);
+                    } else {
+                        let updated = self.huds.update_hud(&hud, false);
+                        self.git_updated(&hud, updated);
+                    }
+                }
+                Msg::Duplicate(hud) => {
+                    let new_name = format!("{hud} copy");
+                    self.duplicate = Some((hud, new_name));
+                }
+                Msg::Rename(hud) => {
+                    let new_name = hud.clone();
+                    self.rename = Some((hud, new_name));
+                }
+                Msg::AddTag(hud, tag) => {
+                    if let Err(e) = self.huds.add_tag(&hud, &tag) {
+                        self.error(e);
+                    }
+                }
+                Msg::RemoveTag(hud, tag) => {
+                    if let Err(e) = self.huds.remove_tag(&hud, &tag) {
+                        self.error(e);
+                    }
+
+                    let tags = self.huds.all_tags();
+                    self.tag_filter.retain(|t| tags.contains(&t.as_str()));
+                }
+                Msg::Disable(hud) => {
+                    if let Err(e) = self.huds.disable_hud(&hud) {
+                        self.error(e);
+                    }
+                }
+                Msg::Enable(hud) => {
+                    if let Err(e) = self.huds.enable_hud(&hud) {
                         self.error(e);
                     }
                 }
-                Msg::Error(e) => self.error(e),
+                Msg::Delete(hud) => self.confirm_delete = Some(hud),
+                Msg::Deactivate => {
+                    let scanned = self
+                        .huds
+                        .deactivate()
+                        .and_then(|_| self.huds.scan_incremental());
+                    self.scanned(scanned);
+                }
+                Msg::TogglePair => {
+                    let scanned = self
+                        .huds
+                        .toggle_pair()
+                        .and_then(|_| self.huds.scan_incremental());
+                    self.scanned(scanned);
+                }
+                Msg::Undo => {
+                    let scanned = self
+                        .huds
+                        .undo_set_active()
+                        .and_then(|_| self.huds.scan_incremental());
+                    self.scanned(scanned);
+                }
+                Msg::Rescan => {
+                    let scanned = self.huds.scan_incremental();
+                    self.scanned(scanned);
+                }
+                Msg::Error(e) => self.error(e),
+            }
+        }
+
+        for file in ctx.input(|i| i.raw.dropped_files.clone()) {
+            match file.path {
+                Some(path) if path.extension().is_some_and(|e| e == "json") => {
+                    self.import_favorites(&path, true)
+                }
+                Some(path) => self.install_zip(ctx, path),
+                None => {}
             }
         }
 
+        self.update_title(ctx);
+        self.toasts(ctx);
+        self.confirm_switch_window(ctx);
+        self.confirm_git_update_window(ctx);
+        self.confirm_delete_window(ctx);
+        self.note_window(ctx);
+        self.duplicate_window(ctx);
+        self.rename_window(ctx);
+        self.install_conflict_window(ctx);
+
         TopBottomPanel::bottom("status_bar")
             .show_separator_line(false)
             .frame(
@@ -120,22 +1130,251 @@ fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
             });
 
         CentralPanel::default().show(ctx, |ui| {
+            ui.set_enabled(
+                self.confirm_switch.is_none()
+                    && self.confirm_git_update.is_none()
+                    && self.confirm_delete.is_none()
+                    && self.note.is_none()
+                    && self.duplicate.is_none()
+                    && self.rename.is_none()
+                    && self.install_conflict.is_none(),
+            );
+
             ui.group(|ui| {
                 ui.with_layout(Layout::right_to_left(Align::Min), |ui| {
                     ui.horizontal(|ui| {
+                        ComboBox::from_id_source("sort_mode")
+                            .selected_text(format!("sort: {}", self.settings.sort_mode))
+                            .show_ui(ui, |ui| {
+                                for mode in SortMode::ALL {
+                                    ui.selectable_value(
+                                        &mut self.settings.sort_mode,
+                                        mode,
+                                        mode.to_string(),
+                                    );
+                                }
+                            });
+                        if self.huds.sort_mode != self.settings.sort_mode {
+                            self.huds.sort_by(self.settings.sort_mode);
+                        }
+                        if !self.profiles.is_empty() {
+                            let profile = self.settings.profile.clone();
+                            ComboBox::from_id_source("profile")
+                                .selected_text(format!(
+                                    "profile: {}",
+                                    profile.as_deref().unwrap_or("default")
+                                ))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut self.settings.profile,
+                                        None,
+                                        "default",
+                                    );
+                                    for p in &self.profiles {
+                                        ui.selectable_value(
+                                            &mut self.settings.profile,
+                                            Some(p.name.clone()),
+                                            &p.name,
+                                        );
+                                    }
+                                });
+                            if self.settings.profile != profile {
+                                self.start_scan(ui.ctx());
+                            }
+                        }
+                        ui.menu_button("settings", |ui| {
+                            let slider =
+                                Slider::new(&mut self.settings.search_threshold, 0.0..=1.0)
+                                    .text("search threshold");
+                            if ui
+                                .add(slider)
+                                .on_hover_text("how close to the best match a hud must score")
+                                .changed()
+                            {
+                                self.search();
+                            }
+                            ui.checkbox(
+                                &mut self.settings.confirm_switch,
+                                "confirm before switching",
+                            );
+                            ui.checkbox(
+                                &mut self.settings.pin_active,
+                                "pin the active hud to the top",
+                            );
+                            if ui
+                                .checkbox(&mut self.settings.safe_mode, "safe mode")
+                                .on_hover_text("log what switching would move without moving it")
+                                .changed()
+                            {
+                                self.huds.dry_run = self.settings.safe_mode;
+                            }
+                            let mut backup = self.huds.backup_before_switch;
+                            if ui
+                                .checkbox(&mut backup, "back up scripts before switching")
+                                .on_hover_text(
+                                    "keep copies of the hud's scripts from before each switch in \
+                                     huds/.backups",
+                                )
+                                .changed()
+                            {
+                                if let Err(e) = self.huds.set_backup_before_switch(backup) {
+                                    self.error(e);
+                                }
+                            }
+                            let strategy = self.huds.activation_strategy;
+                            let mut selected = strategy;
+                            ComboBox::from_label("when switching")
+                                .selected_text(strategy.to_string())
+                                .show_ui(ui, |ui| {
+                                    for s in ActivationStrategy::ALL {
+                                        ui.selectable_value(&mut selected, s, s.to_string());
+                                    }
+                                })
+                                .response
+                                .on_hover_text("copy and link leave the huds folder untouched");
+                            if selected != strategy {
+                                match self.huds.set_activation_strategy(selected) {
+                                    Ok(()) => self.start_scan(ui.ctx()),
+                                    Err(e) => self.error(e),
+                                }
+                            }
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    TextEdit::singleline(&mut self.custom_dir)
+                                        .hint_text("custom folder"),
+                                );
+                                if ui
+                                    .button("Set")
+                                    .on_hover_text("use this `tf/custom` instead of finding one")
+                                    .clicked()
+                                {
+                                    let custom_dir = PathBuf::from(self.custom_dir.trim());
+                                    match self.huds.set_custom_dir(custom_dir) {
+                                        Ok(()) => {
+                                            self.settings.profile = None;
+                                            self.start_scan(ui.ctx());
+                                        }
+                                        Err(e) => self.error(e),
+                                    }
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    TextEdit::singleline(&mut self.favorites_file)
+                                        .hint_text("favorites file (.json)"),
+                                );
+                                let path = PathBuf::from(self.favorites_file.trim());
+                                let has_path = !self.favorites_file.trim().is_empty();
+                                if ui.add_enabled(has_path, Button::new("Export")).clicked() {
+                                    self.export_favorites(&path);
+                                }
+                                if ui
+                                    .add_enabled(has_path, Button::new("Import"))
+                                    .on_hover_text("add these to your favorites")
+                                    .clicked()
+                                {
+                                    self.import_favorites(&path, true);
+                                }
+                                if ui
+                                    .add_enabled(has_path, Button::new("Replace"))
+                                    .on_hover_text("make these your only favorites")
+                                    .clicked()
+                                {
+                                    self.import_favorites(&path, false);
+                                }
+                            });
+                            ui.separator();
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    TextEdit::singleline(&mut self.github_url)
+                                        .hint_text("github repo url"),
+                                );
+                                let installing = self.install.is_some();
+                                if ui
+                                    .add_enabled(!installing, Button::new("Install"))
+                                    .on_hover_text("download and install a hud from github")
+                                    .clicked()
+                                {
+                                    let url = std::mem::take(&mut self.github_url);
+                                    self.install(
+                                        ui.ctx(),
+                                        InstallSource::Github(url),
+                                        ExistingHud::Refuse,
+                                    );
+                                }
+                            });
+                        });
+                        let theme = if self.settings.dark_mode {
+                            "light"
+                        } else {
+                            "dark"
+                        };
+                        if ui.button(theme).on_hover_text("switch theme").clicked() {
+                            self.settings.dark_mode = !self.settings.dark_mode;
+                            ui.ctx().set_visuals(visuals(self.settings.dark_mode));
+                        }
+                        if ui
+                            .toggle_value(&mut self.settings.favorites_only, "favorites only")
+                            .changed()
+                            && !self.search.is_empty()
+                        {
+                            self.search();
+                        }
+                        if ui
+                            .toggle_value(&mut self.settings.active_only, "active only")
+                            .changed()
+                            && !self.search.is_empty()
+                        {
+                            self.search();
+                        }
                         if ui.button("Search").clicked() {
                             self.search();
                         }
                     });
 
+                    if ui
+                        .add_enabled(!self.search.is_empty(), Button::new("x"))
+                        .on_hover_text("clear search")
+                        .clicked()
+                    {
+                        self.clear_search();
+                    }
+
                     let text_edit = ui.add_sized(
                         [ui.available_width(), 0.0],
                         TextEdit::singleline(&mut self.search).hint_text("hud name"),
                     );
 
+                    if std::mem::take(&mut self.focus_search) {
+                        text_edit.request_focus();
+                    }
+
                     if text_edit.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
                         text_edit.request_focus();
                         self.search();
+                    } else if text_edit.lost_focus() && ui.input(|i| i.key_pressed(Key::Escape)) {
+                        self.clear_search();
+                    }
+
+                    let recent_id = ui.make_persistent_id("recent_searches");
+                    if text_edit.gained_focus() && !self.settings.recent_searches.is_empty() {
+                        ui.memory_mut(|m| m.open_popup(recent_id));
+                    } else if text_edit.changed() {
+                        ui.memory_mut(|m| m.close_popup());
+                    }
+
+                    let recent = popup_below_widget(ui, recent_id, &text_edit, |ui| {
+                        let mut selected = None;
+                        for search in &self.settings.recent_searches {
+                            if ui.selectable_label(false, search).clicked() {
+                                selected = Some(search.clone());
+                            }
+                        }
+                        selected
+                    });
+                    if let Some(search) = recent.flatten() {
+                        self.search = search;
+                        self.search();
                     }
                 });
             });
@@ -153,104 +1392,355 @@ fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
                             .clicked()
                         {
                             if let Err(e) = open::that(&hud.path) {
-                                self.error(e.into());
+                                self.msg = Some(Msg::Error(e.into()));
+                            }
+                        }
+                        if ui
+                            .add(Button::new("vdf").fill(Color32::TRANSPARENT))
+                            .on_hover_text("edit info.vdf")
+                            .clicked()
+                        {
+                            if let Err(e) = open_info_vdf(hud) {
+                                self.msg = Some(Msg::Error(e));
                             }
                         }
                     }
-                    ui.allocate_space(ui.available_size());
+                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                        if ui
+                            .add_enabled(
+                                self.huds.active_hud.is_some(),
+                                Button::new("Use default hud"),
+                            )
+                            .on_hover_text("move the active hud back into `huds`")
+                            .clicked()
+                        {
+                            self.msg = Some(Msg::Deactivate);
+                        }
+                    });
                 });
             });
             ui.group(|ui| {
-                ui.columns(2, |col| {
-                    col[0].vertical(|ui| {
-                        let total_rows = self
-                            .huds
-                            .huds
-                            .iter()
-                            .filter(|hud| {
-                                !hud.favorite
-                                    && (self.search_results.is_empty()
-                                        || self.search_results.contains(&hud.name))
-                            })
-                            .count();
-
-                        ScrollArea::vertical().show_rows(
-                            ui,
-                            ui.spacing().interact_size.y,
-                            total_rows,
-                            |ui, range| {
-                                Grid::new("huds")
-                                    .num_columns(1)
-                                    .striped(true)
-                                    .start_row(range.start)
-                                    .show(ui, |ui| {
-                                        for hud in self
-                                            .huds
-                                            .huds
-                                            .iter_mut()
-                                            .filter(|hud| {
-                                                !hud.favorite
-                                                    && (self.search_results.is_empty()
-                                                        || self.search_results.contains(&hud.name))
-                                            })
-                                            .skip(range.start)
-                                            .take(range.end)
-                                        {
-                                            let active_hud = self
-                                                .huds
-                                                .active_hud
-                                                .as_ref()
-                                                .map(|hud| hud.name.as_str());
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Tags:");
+                    for tag in self.huds.all_tags() {
+                        let mut filtered = self.tag_filter.contains(tag);
+                        if ui
+                            .toggle_value(&mut filtered, tag)
+                            .on_hover_text("only show huds with this tag")
+                            .changed()
+                        {
+                            if filtered {
+                                self.tag_filter.insert(tag.to_string());
+                            } else {
+                                self.tag_filter.remove(tag);
+                            }
+                        }
+                    }
+                });
 
-                                            hud_list_button(ui, hud, &mut self.msg, active_hud);
-                                            ui.end_row();
-                                        }
-                                    });
-                            },
-                        );
+                let selected = self.selected.and_then(|i| {
+                    self.huds
+                        .huds
+                        .iter()
+                        .filter(|hud| {
+                            is_shown(
+                                &self.search_results,
+                                &self.tag_filter,
+                                &self.settings,
+                                self.huds.active_hud.as_ref(),
+                                hud,
+                            )
+                        })
+                        .nth(i)
+                });
+                let Some((hud, tags)) = selected.map(|h| (h.name.clone(), h.tags.clone())) else {
+                    return;
+                };
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("`{hud}`:"));
+                    if ui.button("notes").on_hover_text("edit notes").clicked() {
+                        let note = self.huds.get_note(&hud).unwrap_or_default().to_string();
+                        self.note = Some((hud.clone(), note));
+                    }
+                    for tag in tags {
+                        if ui
+                            .button(format!("{tag} x"))
+                            .on_hover_text("remove tag")
+                            .clicked()
+                        {
+                            self.msg = Some(Msg::RemoveTag(hud.clone(), tag));
+                        }
+                    }
+
+                    let text_edit = ui.add(
+                        TextEdit::singleline(&mut self.new_tag)
+                            .hint_text("add tag")
+                            .desired_width(100.0),
+                    );
+                    if text_edit.lost_focus()
+                        && ui.input(|i| i.key_pressed(Key::Enter))
+                        && !self.new_tag.trim().is_empty()
+                    {
+                        self.msg = Some(Msg::AddTag(hud, std::mem::take(&mut self.new_tag)));
+                    }
+                });
+            });
+            if self.install.is_some() {
+                let progress = match self.install_progress {
+                    Some((done, total)) => ProgressBar::new(done as f32 / total.max(1) as f32)
+                        .text(format!("installing {done}/{total}")),
+                    None => ProgressBar::new(0.0).text("installing..."),
+                };
+                ui.add(progress);
+            }
+            ui.group(|ui| {
+                if self.scan.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        match &self.scan_progress {
+                            Some(progress) => ui.label(format!(
+                                "scanning... {} folders, at `{}`",
+                                progress.visited, progress.current
+                            )),
+                            None => ui.label("scanning..."),
+                        };
+                    });
+                    ui.allocate_space(ui.available_size());
+                    return;
+                }
+
+                let active = self.huds.active_hud.as_ref().map(|h| h.path.clone());
+                let recent = self
+                    .huds
+                    .recently_used(RECENT_HUDS + 1)
+                    .into_iter()
+                    .filter(|h| Some(&h.path) != active.as_ref())
+                    .take(RECENT_HUDS)
+                    .map(|h| (h.path.clone(), h.title().to_string()))
+                    .collect::<Vec<_>>();
+                if !recent.is_empty() {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("recent:");
+                        for (path, title) in recent {
+                            if ui.small_button(title).on_hover_text("set active").clicked() {
+                                self.msg = Some(Msg::SetActive(path));
+                            }
+                        }
                     });
-                    col[1].vertical(|ui| {
+                    ui.separator();
+                }
+
+                // the pinned hud keeps its place in `selected` so keyboard navigation still
+                // goes through it in list order
+                self.flash_active_until = self.flash_active_until.filter(|t| *t > Instant::now());
+                if let Some(until) = self.flash_active_until {
+                    ui.ctx()
+                        .request_repaint_after(until.saturating_duration_since(Instant::now()));
+                }
+                let flashing = self.flash_active_until.is_some();
+                let pinned = self
+                    .huds
+                    .active_hud
+                    .as_ref()
+                    .filter(|_| self.settings.pin_active)
+                    .map(|h| h.path.clone());
+                let is_pinned = |hud: &Hud| Some(&hud.path) == pinned.as_ref();
+
+                if let Some((i, hud)) = self
+                    .huds
+                    .huds
+                    .iter_mut()
+                    .filter(|hud| {
+                        is_shown(
+                            &self.search_results,
+                            &self.tag_filter,
+                            &self.settings,
+                            self.huds.active_hud.as_ref(),
+                            hud,
+                        )
+                    })
+                    .enumerate()
+                    .find(|(_, hud)| is_pinned(hud))
+                {
+                    let active_hud = Some(hud.name.clone());
+                    hud_list_button(
+                        ui,
+                        hud,
+                        &mut self.msg,
+                        &mut self.cache,
+                        active_hud.as_deref(),
+                        self.selected == Some(i) || flashing,
+                        self.updates.contains(&hud.name),
+                    );
+                    ui.separator();
+                }
+
+                let reveal = std::mem::take(&mut self.reveal_active)
+                    .then(|| self.huds.active_hud.as_ref().map(|h| h.path.clone()))
+                    .flatten();
+                if reveal.is_some() {
+                    self.flash_active_until = Some(Instant::now() + FLASH_DURATION);
+                }
+                let scroll_to = std::mem::take(&mut self.scroll_to_selected)
+                    .then(|| self.selected.and_then(|i| self.shown_huds().nth(i)))
+                    .flatten()
+                    .map(|h| h.path.clone());
+
+                let columns = if self.settings.favorites_only { 1 } else { 2 };
+                ui.columns(columns, |col| {
+                    let (huds_col, fav_col) = match col {
+                        [fav_col] => (None, fav_col),
+                        [huds_col, fav_col] => (Some(huds_col), fav_col),
+                        _ => unreachable!(),
+                    };
+
+                    if let Some(huds_col) = huds_col {
+                        huds_col.vertical(|ui| {
+                            let rows = self.huds.huds.iter().filter(|hud| {
+                                !hud.favorite
+                                    && !is_pinned(hud)
+                                    && is_shown(
+                                        &self.search_results,
+                                        &self.tag_filter,
+                                        &self.settings,
+                                        self.huds.active_hud.as_ref(),
+                                        hud,
+                                    )
+                            });
+                            let total_rows = rows.clone().count();
+                            let scroll = reveal_scroll(
+                                ui,
+                                rows.clone().position(|h| Some(&h.path) == reveal.as_ref()),
+                            );
+                            let scroll_row = rows
+                                .clone()
+                                .position(|h| Some(&h.path) == scroll_to.as_ref());
+
+                            scroll.show_rows(
+                                ui,
+                                ui.spacing().interact_size.y,
+                                total_rows,
+                                |ui, range| {
+                                    if let Some(row) = scroll_row {
+                                        scroll_to_row(ui, &range, row);
+                                    }
+                                    Grid::new("huds")
+                                        .num_columns(1)
+                                        .striped(true)
+                                        .start_row(range.start)
+                                        .show(ui, |ui| {
+                                            let shown = self.huds.huds.iter_mut().filter(|hud| {
+                                                is_shown(
+                                                    &self.search_results,
+                                                    &self.tag_filter,
+                                                    &self.settings,
+                                                    self.huds.active_hud.as_ref(),
+                                                    hud,
+                                                )
+                                            });
+                                            for (i, hud) in rows_in_view(
+                                                shown,
+                                                |hud| !hud.favorite && !is_pinned(hud),
+                                                range,
+                                            ) {
+                                                let active_hud = self
+                                                    .huds
+                                                    .active_hud
+                                                    .as_ref()
+                                                    .map(|hud| hud.name.as_str());
+                                                let flashing = flashing
+                                                    && Some(hud.name.as_str()) == active_hud;
+
+                                                hud_list_button(
+                                                    ui,
+                                                    hud,
+                                                    &mut self.msg,
+                                                    &mut self.cache,
+                                                    active_hud,
+                                                    self.selected == Some(i) || flashing,
+                                                    self.updates.contains(&hud.name),
+                                                );
+                                                ui.end_row();
+                                            }
+                                        });
+                                },
+                            );
+                        });
+                    }
+                    fav_col.vertical(|ui| {
                         ui.push_id("fav_huds_scroll", |ui| {
-                            let total_rows = self
+                            let rows = self
                                 .huds
                                 .huds
                                 .iter()
                                 .filter(|hud| {
-                                    self.search_results.is_empty()
-                                        || self.search_results.contains(&hud.name)
+                                    is_shown(
+                                        &self.search_results,
+                                        &self.tag_filter,
+                                        &self.settings,
+                                        self.huds.active_hud.as_ref(),
+                                        hud,
+                                    )
                                 })
                                 .take_while(|hud| hud.favorite)
-                                .count();
+                                .filter(|hud| !is_pinned(hud));
+                            let total_rows = rows.clone().count();
+                            let scroll = reveal_scroll(
+                                ui,
+                                rows.clone().position(|h| Some(&h.path) == reveal.as_ref()),
+                            );
+                            let scroll_row = rows
+                                .clone()
+                                .position(|h| Some(&h.path) == scroll_to.as_ref());
 
-                            ScrollArea::vertical().show_rows(
+                            scroll.show_rows(
                                 ui,
                                 ui.spacing().interact_size.y,
                                 total_rows,
                                 |ui, range| {
+                                    if let Some(row) = scroll_row {
+                                        scroll_to_row(ui, &range, row);
+                                    }
                                     Grid::new("fav_huds")
                                         .num_columns(1)
                                         .striped(true)
                                         .start_row(range.start)
                                         .show(ui, |ui| {
-                                            for hud in self
+                                            let shown = self
                                                 .huds
                                                 .huds
                                                 .iter_mut()
                                                 .filter(|hud| {
-                                                    self.search_results.is_empty()
-                                                        || self.search_results.contains(&hud.name)
+                                                    is_shown(
+                                                        &self.search_results,
+                                                        &self.tag_filter,
+                                                        &self.settings,
+                                                        self.huds.active_hud.as_ref(),
+                                                        hud,
+                                                    )
                                                 })
-                                                .take_while(|hud| hud.favorite)
-                                                .skip(range.start)
-                                                .take(range.end)
+                                                .take_while(|hud| hud.favorite);
+                                            for (i, hud) in
+                                                rows_in_view(shown, |hud| !is_pinned(hud), range)
                                             {
                                                 let active_hud = self
                                                     .huds
                                                     .active_hud
                                                     .as_ref()
                                                     .map(|hud| hud.name.as_str());
+                                                let flashing = flashing
+                                                    && Some(hud.name.as_str()) == active_hud;
 
-                                                hud_list_button(ui, hud, &mut self.msg, active_hud);
+                                                hud_list_button(
+                                                    ui,
+                                                    hud,
+                                                    &mut self.msg,
+                                                    &mut self.cache,
+                                                    active_hud,
+                                                    self.selected == Some(i) || flashing,
+                                                    self.updates.contains(&hud.name),
+                                                );
                                                 ui.end_row();
                                             }
                                         });
@@ -263,9 +1753,21 @@ fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
             });
         });
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.settings);
+    }
 }
 
-fn hud_list_button(ui: &mut Ui, hud: &mut Hud, msg: &mut Option<Msg>, active_hud: Option<&str>) {
+fn hud_list_button(
+    ui: &mut Ui,
+    hud: &mut Hud,
+    msg: &mut Option<Msg>,
+    cache: &mut HudCache,
+    active_hud: Option<&str>,
+    selected: bool,
+    update_available: bool,
+) {
     let right_align = Layout {
         main_dir: Direction::LeftToRight,
         main_wrap: false,
@@ -298,50 +1800,474 @@ fn hud_list_button(ui: &mut Ui, hud: &mut Hud, msg: &mut Option<Msg>, active_hud
                     *msg = Some(Msg::Favorited);
                 }
             });
-            ui.allocate_ui_with_layout(
-                [ui.available_width() - 25.0, 0.0].into(),
-                right_align,
-                |ui| {
-                    let fill = if Some(hud.name.as_str()) == active_hud {
-                        ui.style().visuals.selection.bg_fill
-                    } else {
-                        Color32::TRANSPARENT
-                    };
-
+            if hud.favorite {
+                ui.allocate_ui_with_layout([0.0, 0.0].into(), center_align, |ui| {
                     if ui
-                        .add(Button::new(&hud.name).fill(fill))
-                        .on_hover_text("set active")
+                        .add(Button::new("^").fill(Color32::TRANSPARENT))
+                        .on_hover_text("move up")
                         .clicked()
                     {
-                        *msg = Some(Msg::SetActive(hud.name.clone()));
+                        *msg = Some(Msg::MoveFavoriteUp(hud.name.clone()));
                     }
-                },
-            );
-            ui.allocate_ui_with_layout([ui.available_width(), 0.0].into(), center_align, |ui| {
-                if ui
-                    .add(Button::new("↪").fill(Color32::TRANSPARENT))
-                    .on_hover_text("open folder")
-                    .clicked()
-                {
-                    if let Err(e) = open::that(&hud.path) {
-                        *msg = Some(Msg::Error(e.into()));
+                    if ui
+                        .add(Button::new("v").fill(Color32::TRANSPARENT))
+                        .on_hover_text("move down")
+                        .clicked()
+                    {
+                        *msg = Some(Msg::MoveFavoriteDown(hud.name.clone()));
                     }
+                });
+            }
+            let problems = cache
+                .problems
+                .entry(hud.name.clone())
+                .or_insert_with(|| hud.validate());
+            if !problems.is_empty() {
+                let problems = problems.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+                ui.allocate_ui_with_layout([0.0, 0.0].into(), center_align, |ui| {
+                    ui.colored_label(ui.visuals().warn_fg_color, "!")
+                        .on_hover_text(problems.join("\n"));
+                });
+            }
+            if update_available {
+                let (rect, response) = ui.allocate_exact_size(vec2(8.0, 8.0), Sense::hover());
+                ui.painter()
+                    .circle_filled(rect.center(), 3.0, Color32::from_rgb(80, 150, 255));
+                response.on_hover_text("update available");
+            }
+            ui.allocate_ui_with_layout([ui.available_width(), 0.0].into(), right_align, |ui| {
+                let fill = if Some(hud.name.as_str()) == active_hud {
+                    ui.style().visuals.selection.bg_fill
+                } else {
+                    Color32::TRANSPARENT
+                };
+
+                let stroke = if selected {
+                    ui.style().visuals.selection.stroke
+                } else {
+                    Stroke::NONE
+                };
+
+                let mut title = RichText::new(hud.title());
+                if hud.disabled {
+                    title = title.weak();
+                }
+                let sense = if hud.favorite {
+                    Sense::click_and_drag()
+                } else {
+                    Sense::click()
+                };
+                let response = ui.add(Button::new(title).fill(fill).stroke(stroke).sense(sense));
+                if hud.favorite {
+                    drag_favorite(ui, hud, &response, msg);
+                }
+
+                let mut hover = vec![if hud.disabled {
+                    "disabled, right click to enable".to_string()
+                } else {
+                    "set active, right click for more".to_string()
+                }];
+                if hud.title() != hud.name {
+                    hover.push(format!("folder: {}", hud.name));
+                }
+                if let Some(ui_version) = hud.ui_version {
+                    hover.push(format!("ui version: {ui_version}"));
+                }
+                if let Some(author) = &hud.author {
+                    hover.push(format!("by {author}"));
+                }
+                if let Some(note) = &hud.note {
+                    hover.push(format!("note: {note}"));
+                }
+                if let Some(source) = &hud.source {
+                    hover.push(format!("from `{}`", source.display()));
+                }
+                let hover = hover.join("\n");
+
+                let preview = response.hovered().then(|| {
+                    cache
+                        .previews
+                        .entry(hud.name.clone())
+                        .or_insert_with(|| load_preview(ui.ctx(), hud))
+                        .clone()
+                });
+
+                let response = match preview.flatten() {
+                    Some(texture) => response.on_hover_ui(|ui| {
+                        ui.label(&hover);
+                        ui.image(&texture);
+                    }),
+                    None => response.on_hover_text(&hover),
+                };
+                let response = response.context_menu(|ui| hud_context_menu(ui, hud, msg, cache));
+
+                if response.clicked() && !hud.disabled {
+                    *msg = Some(Msg::SetActive(hud.path.clone()));
                 }
             });
         });
     });
 }
 
+// favorites are dragged by their name button, the drop goes wherever the pointer was last over
+// another favorite
+fn drag_favorite(ui: &Ui, hud: &Hud, response: &Response, msg: &mut Option<Msg>) {
+    let dragged_id = Id::new("dragged_favorite");
+    let target_id = Id::new("favorite_drop_target");
+
+    if response.drag_started() {
+        ui.data_mut(|d| {
+            d.insert_temp(dragged_id, hud.name.clone());
+            d.remove::<usize>(target_id);
+        });
+    }
+
+    let dragging = ui.data(|d| d.get_temp::<String>(dragged_id)).is_some();
+    if let Some(rank) = hud.favorite_rank.filter(|_| dragging) {
+        if ui.rect_contains_pointer(response.rect) {
+            ui.data_mut(|d| d.insert_temp(target_id, rank));
+        }
+        if ui.data(|d| d.get_temp::<usize>(target_id)) == Some(rank) {
+            let rect = response.rect;
+            let stroke = ui.visuals().selection.stroke;
+            ui.painter().hline(rect.x_range(), rect.top(), stroke);
+        }
+    }
+
+    if response.drag_released() {
+        let target = ui.data_mut(|d| {
+            d.remove::<String>(dragged_id);
+            d.remove_temp::<usize>(target_id)
+        });
+        if let Some(target) = target.filter(|&t| Some(t) != hud.favorite_rank) {
+            *msg = Some(Msg::MoveFavorite(hud.name.clone(), target));
+        }
+    }
+}
+
+fn hud_context_menu(ui: &mut Ui, hud: &Hud, msg: &mut Option<Msg>, cache: &mut HudCache) {
+    let file_count = cache
+        .file_counts
+        .entry(hud.name.clone())
+        .or_insert_with(|| hud.file_count());
+    ui.label(format!("{file_count} files"));
+    ui.separator();
+
+    if ui.button("activate").clicked() {
+        *msg = Some(Msg::SetActive(hud.path.clone()));
+        ui.close_menu();
+    }
+    if ui.button("open folder").clicked() {
+        if let Err(e) = open::that(&hud.path) {
+            *msg = Some(Msg::Error(e.into()));
+        }
+        ui.close_menu();
+    }
+    if ui.button("open screenshots folder").clicked() {
+        if let Err(e) = open::that(hud.screenshots_dir()) {
+            *msg = Some(Msg::Error(e.into()));
+        }
+        ui.close_menu();
+    }
+    if ui.button("open info.vdf").clicked() {
+        if let Err(e) = open_info_vdf(hud) {
+            *msg = Some(Msg::Error(e));
+        }
+        ui.close_menu();
+    }
+    if ui.button("duplicate").clicked() {
+        *msg = Some(Msg::Duplicate(hud.name.clone()));
+        ui.close_menu();
+    }
+    if ui.button("rename").clicked() {
+        *msg = Some(Msg::Rename(hud.name.clone()));
+        ui.close_menu();
+    }
+    if ui.button("delete").clicked() {
+        *msg = Some(Msg::Delete(hud.name.clone()));
+        ui.close_menu();
+    }
+    if hud.disabled {
+        if ui.button("enable").clicked() {
+            *msg = Some(Msg::Enable(hud.name.clone()));
+            ui.close_menu();
+        }
+    } else if ui
+        .button("disable")
+        .on_hover_text("keep it installed but out of the list of huds to switch to")
+        .clicked()
+    {
+        *msg = Some(Msg::Disable(hud.name.clone()));
+        ui.close_menu();
+    }
+    if hud.is_git_repo && ui.button("update with git pull").clicked() {
+        *msg = Some(Msg::GitUpdate(hud.name.clone()));
+        ui.close_menu();
+    }
+}
+
+// scrolls a virtualised list so `row` is at the top, for revealing the active hud
+fn reveal_scroll(ui: &Ui, row: Option<usize>) -> ScrollArea {
+    let scroll = ScrollArea::vertical();
+    let Some(row) = row else {
+        return scroll;
+    };
+
+    let row_height = ui.spacing().interact_size.y + ui.spacing().item_spacing.y;
+    scroll.vertical_scroll_offset(row as f32 * row_height)
+}
+
+// scrolls only as far as it takes to show `row`, which might not have been built yet
+fn scroll_to_row(ui: &Ui, range: &std::ops::Range<usize>, row: usize) {
+    let row_height = ui.spacing().interact_size.y + ui.spacing().item_spacing.y;
+    let top = ui.max_rect().top() + (row as f32 - range.start as f32) * row_height;
+
+    ui.scroll_to_rect(
+        Rect::from_x_y_ranges(ui.max_rect().x_range(), top..=top + row_height),
+        None,
+    );
+}
+
+// every list and its row count go through this, so they never disagree about what's shown
+fn is_shown(
+    search_results: &HashSet<String>,
+    tag_filter: &HashSet<String>,
+    settings: &Settings,
+    active_hud: Option<&Hud>,
+    hud: &Hud,
+) -> bool {
+    (search_results.is_empty() || search_results.contains(&hud.name))
+        && tag_filter.iter().all(|t| hud.tags.contains(t))
+        && (hud.favorite || !settings.favorites_only)
+        && (!settings.active_only || active_hud.is_some_and(|h| h.path == hud.path))
+}
+
+fn open_info_vdf(hud: &Hud) -> anyhow::Result<()> {
+    let info_vdf = hud.info_vdf()?;
+    open::that(&info_vdf).with_context(|| format!("failed to open `{}`", info_vdf.display()))
+}
+
+fn visuals(dark_mode: bool) -> Visuals {
+    if dark_mode {
+        Visuals::dark()
+    } else {
+        Visuals::light()
+    }
+}
+
+fn load_preview(ctx: &Context, hud: &Hud) -> Option<TextureHandle> {
+    let image = hud.load_thumbnail(PREVIEW_SIZE)?;
+    let size = [image.width() as usize, image.height() as usize];
+
+    Some(ctx.load_texture(
+        &hud.name,
+        ColorImage::from_rgba_unmultiplied(size, &image),
+        TextureOptions::LINEAR,
+    ))
+}
+
+const USAGE: &str = "usage:
+    hud_manager                               launch the gui
+    hud_manager list                          print all huds, the active one marked with `*`
+    hud_manager active                        print the active hud
+    hud_manager set <hud>                     set the active hud
+    hud_manager favorite <hud>                favorite or unfavorite a hud
+    hud_manager disable <hud>                 hide a hud without deleting it
+    hud_manager enable <hud>                  bring back a disabled hud
+    hud_manager export-favorites <file.json>  save favorites to a file to share
+    hud_manager import-favorites <file.json>  add favorites from a file, `--replace` to swap
+                                              them for the current ones
+    hud_manager prune-favorites               forget favorites that aren't installed
+    hud_manager export-state <file.json>      save the whole library to a file
+    hud_manager import-state <file.json>      restore favorites, tags and notes from a file
+
+    add --json to print list, active, set, favorite, disable and enable as json";
+
+fn cli(args: &[String]) -> anyhow::Result<()> {
+    let load = || -> anyhow::Result<Huds> {
+        let mut huds = Huds::default();
+        huds.update_favorites()?;
+        huds.scan_for_huds_cached()?;
+        Ok(huds)
+    };
+
+    let json = args.iter().any(|a| a == "--json");
+    // the old `--list` style still works
+    let args = args
+        .iter()
+        .filter(|a| *a != "--json")
+        .enumerate()
+        .map(|(i, a)| {
+            if i == 0 {
+                a.trim_start_matches("--")
+            } else {
+                a
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match args.as_slice() {
+        ["list"] => {
+            let huds = load()?;
+            let is_active =
+                |hud: &Hud| huds.active_hud.as_ref().is_some_and(|a| a.path == hud.path);
+
+            if json {
+                let huds = huds
+                    .huds
+                    .iter()
+                    .map(|h| hud_json(h, is_active(h)))
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&huds)?);
+                return Ok(());
+            }
+
+            let width = huds.huds.iter().map(|h| h.name.len()).max().unwrap_or(0);
+            for hud in &huds.huds {
+                let marker = if is_active(hud) { "*" } else { " " };
+                let mut flags = Vec::new();
+                if hud.favorite {
+                    flags.push("favorite");
+                }
+                if hud.disabled {
+                    flags.push("disabled");
+                }
+
+                let line = format!("{marker} {:width$}  {}", hud.name, hud.title());
+                let line = match flags.is_empty() {
+                    true => line,
+                    false => format!("{line}  ({})", flags.join(", ")),
+                };
+                println!("{}", line.trim_end());
+            }
+        }
+        ["active"] => {
+            let hud = load()?.active_hud.context("no hud is active")?;
+            match json {
+                true => println!("{}", serde_json::to_string_pretty(&hud_json(&hud, true))?),
+                false => println!("{}", hud.name),
+            }
+        }
+        ["set", hud] => {
+            hud_manager::activate(hud)?;
+            match json {
+                true => println!("{}", serde_json::json!({ "active": hud })),
+                false => println!("`{hud}` is now the active hud"),
+            }
+        }
+        ["favorite", hud] => {
+            let mut huds = load()?;
+            let favorite = !huds.huds.iter().any(|h| h.name == *hud && h.favorite);
+            huds.set_favorite(hud, favorite)?;
+            match (json, favorite) {
+                (true, _) => println!(
+                    "{}",
+                    serde_json::json!({ "hud": hud, "favorite": favorite })
+                ),
+                (false, true) => println!("`{hud}` is now a favorite"),
+                (false, false) => println!("`{hud}` is no longer a favorite"),
+            }
+        }
+        ["disable", hud] => {
+            load()?.disable_hud(hud)?;
+            match json {
+                true => println!("{}", serde_json::json!({ "hud": hud, "disabled": true })),
+                false => println!("`{hud}` is disabled"),
+            }
+        }
+        ["enable", hud] => {
+            load()?.enable_hud(hud)?;
+            match json {
+                true => println!("{}", serde_json::json!({ "hud": hud, "disabled": false })),
+                false => println!("`{hud}` is enabled"),
+            }
+        }
+        ["export-favorites", path] => load()?.export_favorites(Path::new(path))?,
+        ["import-favorites", path, rest @ ..] if rest.is_empty() || rest == ["--replace"] => {
+            let missing = load()?.import_favorites(Path::new(path), rest.is_empty())?;
+            if !missing.is_empty() {
+                println!("these aren't installed: {}", missing.join(", "));
+            }
+        }
+        ["prune-favorites"] => {
+            let pruned = load()?.prune_favorites()?;
+            println!("forgot {pruned} favorites that aren't installed");
+        }
+        ["export-state", path] => load()?.export_state(Path::new(path))?,
+        ["import-state", path] => load()?.import_state(Path::new(path))?,
+        ["help" | "h"] => println!("{USAGE}"),
+        _ => anyhow::bail!("unknown arguments\n\n{USAGE}"),
+    }
+
+    Ok(())
+}
+
+fn hud_json(hud: &Hud, active: bool) -> serde_json::Value {
+    serde_json::json!({
+        "name": hud.name,
+        "title": hud.title(),
+        "author": hud.author,
+        "path": hud.path,
+        "active": active,
+        "favorite": hud.favorite,
+        "disabled": hud.disabled,
+        "source": hud.source,
+    })
+}
+
+// release builds use the windows subsystem so they don't get a console, borrow the parent's
+// one instead so output is visible when run from a terminal
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
 fn main() -> eframe::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    // not being able to log shouldn't stop the huds from being managed
+    let logging = hud_manager::init_logging();
+
+    if !args.is_empty() {
+        attach_console();
+
+        if let Err(e) = &logging {
+            eprintln!("warning: {e:#}");
+        }
+
+        if let Err(e) = cli(&args) {
+            tracing::error!("{e:#}");
+            eprintln!("error: {e:#}");
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
+
     let options = eframe::NativeOptions {
-        viewport: ViewportBuilder::default().with_inner_size([650.0, 700.0]),
+        viewport: ViewportBuilder::default().with_inner_size(WINDOW_SIZE),
+        // `restore_window` relies on this to tell where the monitor is
+        centered: true,
+        persist_window: false,
         ..Default::default()
     };
 
     eframe::run_native(
         "hud manager",
         options,
-        Box::new(|cc| {
+        Box::new(move |cc| {
             let mut font_def = FontDefinitions::empty();
 
             font_def
@@ -362,7 +2288,12 @@ fn main() -> eframe::Result<()> {
                 style.interaction.tooltip_delay = 0.33;
             });
 
-            Box::new(App::new())
+            let mut app = App::new(cc);
+            if let Err(e) = logging {
+                app.error(e);
+            }
+
+            Box::new(app)
         }),
     )
 }
@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::vdf::Vdf;
+
+const TF2_CUSTOM: &str = "steamapps/common/Team Fortress 2/tf/custom";
+
+pub fn tf2_custom_dir() -> Option<PathBuf> {
+    let steam_dir = steam_dir()?;
+
+    libraries(&steam_dir)
+        .into_iter()
+        .map(|library| library.join(TF2_CUSTOM))
+        .find(|custom_dir| custom_dir.is_dir())
+}
+
+fn libraries(steam_dir: &Path) -> Vec<PathBuf> {
+    let mut libraries = vec![steam_dir.to_path_buf()];
+
+    let Ok(vdf) = fs::read_to_string(steam_dir.join("steamapps/libraryfolders.vdf")) else {
+        return libraries;
+    };
+
+    let vdf = Vdf::parse(&vdf);
+    let folders = vdf.get("libraryfolders").map_or(&[][..], Vdf::entries);
+
+    for (_, folder) in folders {
+        // older steam versions store the path directly instead of in a `path` key
+        let path = folder.get("path").unwrap_or(folder);
+        if let Some(path) = path.as_str() {
+            libraries.push(PathBuf::from(path));
+        }
+    }
+
+    libraries
+}
+
+#[cfg(windows)]
+fn steam_dir() -> Option<PathBuf> {
+    let output = std::process::Command::new("reg")
+        .args(["query", r"HKCU\Software\Valve\Steam", "/v", "SteamPath"])
+        .output()
+        .ok()?;
+
+    let output = String::from_utf8_lossy(&output.stdout);
+    let line = output
+        .lines()
+        .find(|l| l.trim_start().starts_with("SteamPath"))?;
+    let (_, path) = line.split_once("REG_SZ")?;
+
+    Some(PathBuf::from(path.trim()))
+}
+
+#[cfg(not(windows))]
+fn steam_dir() -> Option<PathBuf> {
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+
+    [
+        ".steam/steam",
+        ".local/share/Steam",
+        "Library/Application Support/Steam",
+    ]
+    .into_iter()
+    .map(|dir| home.join(dir))
+    .find(|dir| dir.is_dir())
+}
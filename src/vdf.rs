@@ -0,0 +1,124 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vdf {
+    Value(String),
+    Object(Vec<(String, Vdf)>),
+}
+
+impl Vdf {
+    pub fn parse(src: &str) -> Self {
+        let mut tokens = Tokens(src.chars().peekable());
+        Self::Object(parse_object(&mut tokens))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Vdf> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn entries(&self) -> &[(String, Vdf)] {
+        match self {
+            Self::Object(entries) => entries,
+            Self::Value(_) => &[],
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Value(value) => Some(value),
+            Self::Object(_) => None,
+        }
+    }
+}
+
+fn parse_object(tokens: &mut Tokens) -> Vec<(String, Vdf)> {
+    let mut entries = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        let key = match token {
+            Token::Str(key) => key,
+            Token::Close => break,
+            // a stray `{` without a key, just flatten it into this object
+            Token::Open => {
+                entries.extend(parse_object(tokens));
+                continue;
+            }
+        };
+
+        match tokens.next() {
+            Some(Token::Str(value)) => entries.push((key, Vdf::Value(value))),
+            Some(Token::Open) => entries.push((key, Vdf::Object(parse_object(tokens)))),
+            Some(Token::Close) | None => break,
+        }
+    }
+
+    entries
+}
+
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+struct Tokens<'a>(Peekable<Chars<'a>>);
+
+impl Iterator for Tokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        loop {
+            match self.0.next()? {
+                c if c.is_whitespace() => {}
+                '/' if self.0.peek() == Some(&'/') => {
+                    self.0.by_ref().take_while(|&c| c != '\n').for_each(drop);
+                }
+                // platform conditionals like `[$WIN32]` aren't supported, they're skipped
+                '[' => self.0.by_ref().take_while(|&c| c != ']').for_each(drop),
+                '{' => return Some(Token::Open),
+                '}' => return Some(Token::Close),
+                '"' => return Some(Token::Str(self.quoted())),
+                c => return Some(Token::Str(self.unquoted(c))),
+            }
+        }
+    }
+}
+
+impl Tokens<'_> {
+    fn quoted(&mut self) -> String {
+        let mut s = String::new();
+
+        while let Some(c) = self.0.next() {
+            match c {
+                '"' => break,
+                '\\' => match self.0.next() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some(c) => s.push(c),
+                    None => break,
+                },
+                c => s.push(c),
+            }
+        }
+
+        s
+    }
+
+    fn unquoted(&mut self, first: char) -> String {
+        let mut s = String::from(first);
+
+        while let Some(&c) = self.0.peek() {
+            if c.is_whitespace() || matches!(c, '"' | '{' | '}') {
+                break;
+            }
+            s.push(c);
+            self.0.next();
+        }
+
+        s
+    }
+}
@@ -0,0 +1,80 @@
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher as _};
+
+const DEBOUNCE: Duration = Duration::from_millis(500);
+const IGNORED: &[&str] = &[
+    crate::FAVORITES_JSON,
+    crate::TAGS_JSON,
+    crate::NOTES_JSON,
+    crate::ACTIVATED_JSON,
+    crate::SOURCES_JSON,
+    crate::COPIES_JSON,
+    crate::INSTALL_TMP,
+    crate::DISABLED,
+    crate::REPLACED_TMP,
+    crate::backup::BACKUPS,
+];
+
+pub struct Watcher {
+    _watcher: notify::RecommendedWatcher,
+    events: Receiver<Instant>,
+    last_event: Option<Instant>,
+}
+
+impl Watcher {
+    pub(crate) fn new(
+        dirs: &[&Path],
+        on_event: impl Fn(Duration) + Send + 'static,
+    ) -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if event.is_ok_and(is_relevant) && tx.send(Instant::now()).is_ok() {
+                on_event(DEBOUNCE);
+            }
+        })
+        .with_context(|| "failed to start watching for changes")?;
+
+        for dir in dirs.iter().filter(|d| d.exists()) {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("failed to watch `{}`", dir.display()))?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            last_event: None,
+        })
+    }
+
+    // true once things have been quiet for a bit after a change, so a burst of events is only
+    // reported once
+    pub fn changed(&mut self) -> bool {
+        if let Some(last_event) = self.events.try_iter().last() {
+            self.last_event = Some(last_event);
+        }
+
+        match self.last_event {
+            Some(last_event) if last_event.elapsed() >= DEBOUNCE => {
+                self.last_event = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+fn is_relevant(event: Event) -> bool {
+    let ignored = |p: &Path| {
+        p.components()
+            .any(|c| IGNORED.iter().any(|i| c.as_os_str() == *i))
+    };
+
+    !matches!(event.kind, EventKind::Access(_))
+        && (event.paths.is_empty() || !event.paths.iter().all(|p| ignored(p)))
+}
@@ -0,0 +1,573 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use hud_manager::{ActivationStrategy, HudError, Huds, PlannedOp, SortMode};
+
+use common::{custom_dir, folders, scan};
+
+// what `favorites.json` holds, in order
+fn saved_favorites(huds_dir: &Path) -> Vec<String> {
+    let file = fs::read_to_string(huds_dir.join("favorites.json")).unwrap();
+    let file: serde_json::Value = serde_json::from_str(&file).unwrap();
+    assert_eq!(file["version"], 1);
+
+    file["favorites"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn activating_moves_the_hud_into_custom() {
+    let custom_dir = custom_dir(&["a", "b", "c"]);
+    let mut huds = scan(custom_dir.path());
+    assert!(huds.active_hud.is_none());
+
+    huds.set_active_hud("b").unwrap();
+
+    let active = huds.active_hud.as_ref().unwrap();
+    assert_eq!(active.name, "b");
+    assert_eq!(active.path, custom_dir.path().join("b"));
+    assert_eq!(folders(custom_dir.path()), ["b", "huds"]);
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "c"]);
+
+    let rescanned = scan(custom_dir.path());
+    assert_eq!(rescanned.active_hud.unwrap().name, "b");
+}
+
+#[test]
+fn switching_twice_moves_the_first_hud_back() {
+    let custom_dir = custom_dir(&["a", "b", "c"]);
+    let mut huds = scan(custom_dir.path());
+
+    huds.set_active_hud("a").unwrap();
+    huds.set_active_hud("c").unwrap();
+
+    assert_eq!(huds.active_hud.as_ref().unwrap().name, "c");
+    assert_eq!(folders(custom_dir.path()), ["c", "huds"]);
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "b"]);
+
+    let a = huds.huds.iter().find(|h| h.name == "a").unwrap();
+    assert_eq!(a.path, custom_dir.path().join("huds").join("a"));
+}
+
+#[test]
+fn activating_the_active_hud_does_nothing() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+
+    huds.set_active_hud("a").unwrap();
+    huds.set_active_hud("a").unwrap();
+
+    assert_eq!(huds.active_hud.as_ref().unwrap().name, "a");
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["b"]);
+}
+
+#[test]
+fn activating_a_missing_hud_is_an_error() {
+    let custom_dir = custom_dir(&["a"]);
+    let mut huds = scan(custom_dir.path());
+
+    let e = huds.set_active_hud("does-not-exist").unwrap_err();
+
+    assert!(
+        matches!(&e, HudError::HudNotFound(hud) if hud == "does-not-exist"),
+        "{e}"
+    );
+    assert!(huds.active_hud.is_none());
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a"]);
+}
+
+#[test]
+fn activating_a_hud_deleted_since_the_scan_is_an_error() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+
+    fs::remove_dir_all(custom_dir.path().join("huds").join("b")).unwrap();
+
+    assert!(huds.set_active_hud("b").is_err());
+    assert!(huds.active_hud.is_none());
+}
+
+#[test]
+fn disabled_huds_stay_installed_but_cant_be_activated() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+
+    huds.disable_hud("b").unwrap();
+    assert_eq!(folders(&custom_dir.path().join("huds")), [".disabled", "a"]);
+
+    let mut huds = scan(custom_dir.path());
+    let b = huds.huds.iter().find(|h| h.name == "b").unwrap();
+    assert!(b.disabled);
+    assert!(huds.set_active_hud("b").is_err());
+
+    huds.enable_hud("b").unwrap();
+    huds.set_active_hud("b").unwrap();
+    assert_eq!(folders(custom_dir.path()), ["b", "huds"]);
+}
+
+#[test]
+fn the_active_hud_cant_be_disabled() {
+    let custom_dir = custom_dir(&["a"]);
+    let mut huds = scan(custom_dir.path());
+
+    huds.set_active_hud("a").unwrap();
+
+    assert!(huds.disable_hud("a").is_err());
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+}
+
+#[test]
+fn the_active_hud_cant_be_deleted() {
+    let custom_dir = custom_dir(&["a"]);
+    let mut huds = scan(custom_dir.path());
+
+    huds.set_active_hud("a").unwrap();
+
+    assert!(matches!(
+        huds.delete_hud("a"),
+        Err(HudError::HudAlreadyActive { .. })
+    ));
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+}
+
+#[test]
+fn renaming_the_active_hud_keeps_it_active() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+
+    huds.set_active_hud("a").unwrap();
+    huds.rename_hud("a", "alpha").unwrap();
+
+    let active = huds.active_hud.as_ref().unwrap();
+    assert_eq!(active.name, "alpha");
+    assert_eq!(active.path, custom_dir.path().join("alpha"));
+    assert_eq!(folders(custom_dir.path()), ["alpha", "huds"]);
+
+    let rescanned = scan(custom_dir.path());
+    assert_eq!(rescanned.active_hud.unwrap().name, "alpha");
+}
+
+#[test]
+fn renaming_a_favorite_keeps_it_a_favorite() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let huds_dir = custom_dir.path().join("huds");
+    fs::write(huds_dir.join("favorites.txt"), "b").unwrap();
+    let mut huds = scan(custom_dir.path());
+
+    huds.rename_hud("b", "bee").unwrap();
+
+    assert_eq!(huds.favorites().collect::<Vec<_>>(), ["bee"]);
+    assert_eq!(saved_favorites(&huds_dir), ["bee"]);
+    assert_eq!(folders(&huds_dir), ["a", "bee"]);
+
+    let rescanned = scan(custom_dir.path());
+    assert!(rescanned.huds.iter().any(|h| h.name == "bee" && h.favorite));
+}
+
+#[test]
+fn renaming_rejects_bad_and_taken_names() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+
+    for name in ["", "x/y", "x\\y", "x:y", "..", "x."] {
+        assert!(huds.rename_hud("a", name).is_err(), "`{name}` was accepted");
+    }
+    assert!(matches!(
+        huds.rename_hud("a", "b"),
+        Err(HudError::NameCollision(_))
+    ));
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "b"]);
+}
+
+#[test]
+fn moving_a_favorite_reorders_the_saved_favorites() {
+    let custom_dir = custom_dir(&["a", "b", "c"]);
+    let huds_dir = custom_dir.path().join("huds");
+    fs::write(huds_dir.join("favorites.txt"), "a\nb\nc").unwrap();
+    let mut huds = scan(custom_dir.path());
+
+    huds.move_favorite("c", 0).unwrap();
+    assert_eq!(huds.favorites().collect::<Vec<_>>(), ["c", "a", "b"]);
+
+    huds.move_favorite("c", 10).unwrap();
+    assert_eq!(saved_favorites(&huds_dir), ["a", "b", "c"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_failed_switch_puts_the_previous_hud_back() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+    huds.activation_strategy = ActivationStrategy::Copy;
+
+    huds.set_active_hud("a").unwrap();
+    // copying a link to nowhere fails partway through
+    std::os::unix::fs::symlink("missing", custom_dir.path().join("huds/b/broken")).unwrap();
+
+    let e = huds.set_active_hud("b").unwrap_err();
+    assert!(
+        format!("{e:#}").contains("`a` is still the active hud"),
+        "{e:#}"
+    );
+
+    let active = huds.active_hud.as_ref().unwrap();
+    assert_eq!(active.path, custom_dir.path().join("a"));
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+    assert!(custom_dir.path().join("a").join("info.vdf").is_file());
+}
+
+#[cfg(unix)]
+#[test]
+fn linking_leaves_the_hud_in_the_library() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+    huds.activation_strategy = ActivationStrategy::Symlink;
+
+    huds.set_active_hud("a").unwrap();
+    let link = custom_dir.path().join("a");
+    assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "b"]);
+
+    let rescanned = scan(custom_dir.path());
+    assert_eq!(rescanned.active_hud.unwrap().path, link);
+    assert_eq!(rescanned.huds.len(), 2);
+
+    huds.set_active_hud("b").unwrap();
+    assert!(!link.exists());
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "b"]);
+}
+
+#[test]
+fn a_copy_is_removed_even_after_the_strategy_changes() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+    huds.activation_strategy = ActivationStrategy::Copy;
+    huds.set_active_hud("a").unwrap();
+
+    let mut huds = scan(custom_dir.path());
+    assert_eq!(huds.huds.len(), 2);
+    huds.activation_strategy = ActivationStrategy::Move;
+    huds.set_active_hud("b").unwrap();
+    assert_eq!(folders(custom_dir.path()), ["b", "huds"]);
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a"]);
+
+    // `b` was moved, so it goes back even though copying is picked now
+    huds.activation_strategy = ActivationStrategy::Copy;
+    huds.set_active_hud("a").unwrap();
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "b"]);
+}
+
+#[test]
+fn favorites_stay_in_front_whatever_the_sort() {
+    let custom_dir = custom_dir(&["a", "b", "c"]);
+    fs::write(custom_dir.path().join("huds").join("favorites.txt"), "c").unwrap();
+    let mut huds = scan(custom_dir.path());
+
+    for sort_mode in SortMode::ALL {
+        huds.sort_by(sort_mode);
+        assert_eq!(huds.huds[0].name, "c");
+    }
+}
+
+#[test]
+fn a_stale_copy_of_the_active_hud_is_reported_but_not_activated() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+    huds.set_active_hud("a").unwrap();
+    // left behind by hand, or by an older manager
+    let stale = custom_dir.path().join("huds").join("a");
+    fs::create_dir_all(&stale).unwrap();
+    fs::File::create(stale.join("info.vdf")).unwrap();
+
+    let mut huds = scan(custom_dir.path());
+    assert_eq!(huds.duplicates(), ["a"]);
+    assert_eq!(
+        huds.active_hud.as_ref().unwrap().path,
+        custom_dir.path().join("a")
+    );
+
+    huds.set_active_hud("a").unwrap();
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "b"]);
+}
+
+#[test]
+fn pruning_forgets_favorites_that_arent_installed() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let huds_dir = custom_dir.path().join("huds");
+    fs::write(huds_dir.join("favorites.txt"), "gone\nb").unwrap();
+    let mut huds = scan(custom_dir.path());
+
+    huds.save_favorites().unwrap();
+    assert_eq!(saved_favorites(&huds_dir), ["gone", "b"]);
+
+    assert_eq!(huds.prune_favorites().unwrap(), 1);
+    assert_eq!(saved_favorites(&huds_dir), ["b"]);
+    assert_eq!(huds.favorites().collect::<Vec<_>>(), ["b"]);
+}
+
+#[test]
+fn set_favorite_saves_straight_away() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+
+    huds.set_favorite("b", true).unwrap();
+    let huds_dir = custom_dir.path().join("huds");
+    assert_eq!(saved_favorites(&huds_dir), ["b"]);
+
+    huds.set_favorite("b", false).unwrap();
+    assert!(saved_favorites(&huds_dir).is_empty());
+    assert!(matches!(
+        huds.set_favorite("gone", true),
+        Err(HudError::HudNotFound(_))
+    ));
+}
+
+#[test]
+fn importing_favorites_merges_or_replaces_and_reports_missing_huds() {
+    let from = custom_dir(&["a", "b", "other-machine"]);
+    fs::write(
+        from.path().join("huds").join("favorites.txt"),
+        "b\nother-machine",
+    )
+    .unwrap();
+    let exported = from.path().join("favorites.json");
+    scan(from.path()).export_favorites(&exported).unwrap();
+
+    let to = custom_dir(&["a", "b", "c"]);
+    let huds_dir = to.path().join("huds");
+    fs::write(huds_dir.join("favorites.txt"), "c").unwrap();
+    let mut huds = scan(to.path());
+
+    let missing = huds.import_favorites(&exported, true).unwrap();
+    assert_eq!(missing, ["other-machine"]);
+    assert_eq!(huds.favorites().collect::<Vec<_>>(), ["c", "b"]);
+    assert_eq!(saved_favorites(&huds_dir), ["c", "b"]);
+
+    huds.import_favorites(&exported, false).unwrap();
+    assert_eq!(huds.favorites().collect::<Vec<_>>(), ["b"]);
+    assert!(!huds.huds.iter().any(|h| h.name == "c" && h.favorite));
+
+    // older exports were a bare list, then unversioned
+    let old = to.path().join("old.json");
+    fs::write(&old, r#"["a"]"#).unwrap();
+    assert!(huds.import_favorites(&old, true).unwrap().is_empty());
+    fs::write(&old, r#"{"favorites":["c"]}"#).unwrap();
+    assert!(huds.import_favorites(&old, true).unwrap().is_empty());
+    assert_eq!(huds.favorites().collect::<Vec<_>>(), ["b", "a", "c"]);
+}
+
+#[test]
+fn recently_used_follows_each_switch_and_survives_a_rescan() {
+    let custom_dir = custom_dir(&["a", "b", "c", "never"]);
+    let mut huds = scan(custom_dir.path());
+    let recent = |huds: &Huds| {
+        huds.recently_used(10)
+            .into_iter()
+            .map(|h| h.name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    for hud in ["a", "b", "c"] {
+        huds.set_active_hud(hud).unwrap();
+    }
+    assert_eq!(recent(&huds), ["c", "b", "a"]);
+
+    huds.set_active_hud("a").unwrap();
+    assert_eq!(recent(&huds), ["a", "c", "b"]);
+    assert_eq!(huds.recently_used(2).len(), 2);
+
+    assert_eq!(recent(&scan(custom_dir.path())), ["a", "c", "b"]);
+}
+
+#[test]
+fn scan_progress_reports_hud_folders_without_flooding() {
+    let names = (0..30).map(|i| format!("hud-{i}")).collect::<Vec<_>>();
+    let custom_dir = custom_dir(&names.iter().map(String::as_str).collect::<Vec<_>>());
+    let mut huds = scan(custom_dir.path());
+    let reports = |huds: &mut Huds, interval| {
+        huds.scan_progress_interval = interval;
+        let mut reports = Vec::new();
+        huds.scan_for_huds_with_progress(|p| reports.push(p))
+            .unwrap();
+        reports
+    };
+
+    let every = reports(&mut huds, Duration::ZERO);
+    assert_eq!(
+        every.iter().map(|p| p.visited).collect::<Vec<_>>(),
+        (1..=names.len()).collect::<Vec<_>>()
+    );
+    assert!(every.iter().all(|p| names.contains(&p.current)));
+    assert_eq!(huds.huds.len(), names.len());
+
+    let first_only = reports(&mut huds, Duration::MAX);
+    assert_eq!(first_only.len(), 1);
+    assert_eq!(first_only[0].visited, 1);
+}
+
+#[test]
+fn notes_follow_the_hud_through_activate_rename_and_delete() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let notes = custom_dir.path().join("huds").join("notes.json");
+    let mut huds = scan(custom_dir.path());
+
+    huds.set_note("a", "comp config, crosshair #4").unwrap();
+    assert_eq!(
+        huds.huds[0].note.as_deref(),
+        Some("comp config, crosshair #4")
+    );
+
+    huds.set_active_hud("a").unwrap();
+    huds.set_active_hud("b").unwrap();
+    let rescanned = scan(custom_dir.path());
+    let a = rescanned.huds.iter().find(|h| h.name == "a").unwrap();
+    assert_eq!(a.note.as_deref(), Some("comp config, crosshair #4"));
+
+    huds.rename_hud("a", "comp").unwrap();
+    assert_eq!(huds.get_note("a"), None);
+    assert_eq!(huds.get_note("comp"), Some("comp config, crosshair #4"));
+
+    huds.delete_hud("comp").unwrap();
+    assert_eq!(huds.get_note("comp"), None);
+    assert_eq!(fs::read_to_string(&notes).unwrap(), "{}");
+}
+
+#[test]
+fn activating_by_path_needs_no_name_lookup() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+    let b = huds
+        .huds
+        .iter()
+        .find(|h| h.name == "b")
+        .unwrap()
+        .path
+        .clone();
+
+    huds.set_active_by_path(&b).unwrap();
+    assert_eq!(
+        huds.active_hud.as_ref().unwrap().path,
+        custom_dir.path().join("b")
+    );
+
+    let missing = custom_dir.path().join("huds").join("missing");
+    assert!(matches!(
+        huds.set_active_by_path(&missing),
+        Err(HudError::HudNotFound(_))
+    ));
+}
+
+#[test]
+fn planning_a_switch_lists_the_moves_without_making_them() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+    let library = custom_dir.path().join("huds");
+
+    assert_eq!(
+        huds.plan_set_active("a").unwrap(),
+        [PlannedOp::Move {
+            from: library.join("a"),
+            to: custom_dir.path().join("a"),
+        }]
+    );
+    assert_eq!(folders(custom_dir.path()), ["huds"]);
+
+    huds.set_active_hud("a").unwrap();
+    assert_eq!(
+        huds.plan_set_active("b").unwrap(),
+        [
+            PlannedOp::Move {
+                from: custom_dir.path().join("a"),
+                to: library.join("a"),
+            },
+            PlannedOp::Move {
+                from: library.join("b"),
+                to: custom_dir.path().join("b"),
+            },
+        ]
+    );
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+
+    assert!(huds.plan_set_active("a").unwrap().is_empty());
+    assert!(matches!(
+        huds.plan_set_active("missing"),
+        Err(HudError::HudNotFound(_))
+    ));
+}
+
+#[test]
+fn favorites_txt_is_moved_to_favorites_json_once() {
+    let custom_dir = custom_dir(&["a", "b", "c"]);
+    let huds_dir = custom_dir.path().join("huds");
+    fs::write(huds_dir.join("favorites.txt"), "c\nA\n\na").unwrap();
+
+    let mut huds = scan(custom_dir.path());
+    assert_eq!(huds.favorites().collect::<Vec<_>>(), ["c", "a"]);
+    assert_eq!(saved_favorites(&huds_dir), ["c", "a"]);
+    // left for older versions
+    assert!(huds_dir.join("favorites.txt").exists());
+
+    // from now on `favorites.json` is what counts
+    huds.set_favorite("c", false).unwrap();
+    let rescanned = scan(custom_dir.path());
+    assert_eq!(rescanned.favorites().collect::<Vec<_>>(), ["a"]);
+}
+
+#[test]
+fn a_hud_called_huds_is_listed_but_never_activated() {
+    let custom_dir = custom_dir(&["a", "huds"]);
+    let mut huds = scan(custom_dir.path());
+    assert!(huds.active_hud.is_none());
+    assert!(huds.huds.iter().any(|h| h.name == "huds"));
+
+    assert!(matches!(
+        huds.set_active_hud("huds"),
+        Err(HudError::ReservedName(_))
+    ));
+    assert!(matches!(
+        huds.rename_hud("a", "HUDS"),
+        Err(HudError::ReservedName(_))
+    ));
+    assert_eq!(folders(custom_dir.path()), ["huds"]);
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a", "huds"]);
+
+    huds.rename_hud("huds", "other").unwrap();
+    huds.set_active_hud("other").unwrap();
+    assert_eq!(folders(custom_dir.path()), ["huds", "other"]);
+}
+
+#[test]
+fn a_hud_dropped_in_as_custom_huds_isnt_taken_for_the_active_one() {
+    let custom_dir = custom_dir(&["a"]);
+    fs::File::create(custom_dir.path().join("huds").join("info.vdf")).unwrap();
+
+    let mut huds = scan(custom_dir.path());
+    assert!(huds.active_hud.is_none());
+    assert_eq!(
+        huds.huds
+            .iter()
+            .map(|h| h.name.as_str())
+            .collect::<Vec<_>>(),
+        ["a"]
+    );
+
+    assert!(matches!(
+        huds.set_active_hud("a"),
+        Err(HudError::ReservedName(_))
+    ));
+    assert!(matches!(
+        huds.set_favorite("a", true),
+        Err(HudError::ReservedName(_))
+    ));
+    assert_eq!(folders(&custom_dir.path().join("huds")), ["a"]);
+}
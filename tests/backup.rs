@@ -0,0 +1,93 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use hud_manager::ExistingHud;
+
+use common::scan;
+
+fn custom_dir(huds: &[&str]) -> tempfile::TempDir {
+    let custom_dir = common::custom_dir(huds);
+
+    for hud in huds {
+        let scripts = custom_dir.path().join("huds").join(hud).join("scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        fs::write(scripts.join("hudlayout.res"), "original").unwrap();
+    }
+
+    custom_dir
+}
+
+fn zips(dir: &Path) -> Vec<String> {
+    let mut zips = fs::read_dir(dir)
+        .unwrap()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n.ends_with(".zip"))
+        .collect::<Vec<_>>();
+    zips.sort_unstable();
+
+    zips
+}
+
+#[test]
+fn a_backup_of_the_active_hud_installs_again() {
+    let custom_dir = custom_dir(&["a"]);
+    let mut huds = scan(custom_dir.path());
+    assert!(huds.backup_active(custom_dir.path()).is_err());
+
+    huds.set_active_hud("a").unwrap();
+    let layout = custom_dir
+        .path()
+        .join("a")
+        .join("scripts")
+        .join("hudlayout.res");
+    fs::write(&layout, "tweaked").unwrap();
+    fs::create_dir_all(custom_dir.path().join("a").join(".git")).unwrap();
+
+    let backups = tempfile::tempdir().unwrap();
+    let zip = huds.backup_active(backups.path()).unwrap();
+    assert!(zip.file_name().unwrap().to_string_lossy().starts_with("a-"));
+
+    let mut archive = zip::ZipArchive::new(fs::File::open(&zip).unwrap()).unwrap();
+    assert!(archive.by_name("a/info.vdf").is_ok());
+    assert!(archive.file_names().flatten().all(|f| !f.contains(".git")));
+
+    let restored = tempfile::tempdir().unwrap();
+    fs::create_dir_all(restored.path().join("huds")).unwrap();
+    let mut restored_huds = scan(restored.path());
+    let name = restored_huds
+        .install_from_zip(&zip, ExistingHud::Refuse, |_, _| {})
+        .unwrap();
+    assert_eq!(name, "a");
+    assert_eq!(
+        fs::read_to_string(restored.path().join("huds/a/scripts/hudlayout.res")).unwrap(),
+        "tweaked"
+    );
+}
+
+#[test]
+fn switching_away_keeps_only_the_newest_zips() {
+    let custom_dir = custom_dir(&["a", "b"]);
+    let mut huds = scan(custom_dir.path());
+    huds.backup_zips = 2;
+    let backups = custom_dir.path().join("huds").join(".backups").join("a");
+
+    huds.set_active_hud("a").unwrap();
+    assert!(!backups.exists());
+
+    let mut made = Vec::new();
+    for _ in 0..3 {
+        huds.set_active_hud("b").unwrap();
+        made.push(zips(&backups).pop().unwrap());
+        huds.set_active_hud("a").unwrap();
+    }
+
+    assert_eq!(zips(&backups), made[1..]);
+    // `b` got its own
+    assert_eq!(
+        zips(&custom_dir.path().join("huds").join(".backups").join("b")).len(),
+        2
+    );
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use hud_manager::Huds;
+
+use common::huds;
+
+fn write_vdf(vdf: &Path, name: &str) {
+    // keeps the old mtime so the edit is invisible to the cache
+    let modified = fs::metadata(vdf).and_then(|m| m.modified()).ok();
+    fs::write(vdf, format!("\"{name}\"\n{{\n}}\n")).unwrap();
+
+    if let Some(modified) = modified {
+        fs::File::options()
+            .write(true)
+            .open(vdf)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+}
+
+fn scan_cached(custom_dir: &Path) -> Huds {
+    let mut huds = huds(custom_dir);
+    huds.scan_for_huds_cached().unwrap();
+    huds
+}
+
+fn titles(huds: &Huds) -> Vec<&str> {
+    huds.huds.iter().map(|h| h.title()).collect()
+}
+
+#[test]
+fn a_cached_scan_skips_unchanged_huds_on_the_next_launch() {
+    let custom_dir = tempfile::tempdir().unwrap();
+    let vdf = custom_dir.path().join("huds").join("a").join("info.vdf");
+    fs::create_dir_all(vdf.parent().unwrap()).unwrap();
+    write_vdf(&vdf, "first");
+
+    assert_eq!(titles(&scan_cached(custom_dir.path())), ["first"]);
+
+    // only a fresh `Huds` is used, so anything it doesn't parse comes from the cache on disk
+    write_vdf(&vdf, "second");
+    assert_eq!(titles(&scan_cached(custom_dir.path())), ["first"]);
+
+    let mut forced = scan_cached(custom_dir.path());
+    forced.scan_for_huds().unwrap();
+    assert_eq!(titles(&forced), ["second"]);
+
+    fs::File::options()
+        .write(true)
+        .open(&vdf)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(10))
+        .unwrap();
+    assert_eq!(titles(&scan_cached(custom_dir.path())), ["second"]);
+}
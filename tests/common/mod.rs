@@ -0,0 +1,64 @@
+// shared by the test files, each of which only uses some of it
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::Path;
+
+use hud_manager::{Huds, Profile};
+
+// a folder with an empty `info.vdf` in `dir` for each of `huds`
+pub fn hud_dirs(dir: &Path, huds: &[&str]) {
+    for hud in huds {
+        let hud = dir.join(hud);
+        fs::create_dir_all(&hud).unwrap();
+        fs::File::create(hud.join("info.vdf")).unwrap();
+    }
+}
+
+// a `custom` folder with `huds` in its `huds`
+pub fn custom_dir(huds: &[&str]) -> tempfile::TempDir {
+    let custom_dir = tempfile::tempdir().unwrap();
+    let huds_dir = custom_dir.path().join("huds");
+    fs::create_dir_all(&huds_dir).unwrap();
+    hud_dirs(&huds_dir, huds);
+
+    custom_dir
+}
+
+// keeps the tests away from the real config folder, so nothing in it changes how they go and they
+// don't leave anything behind in it
+pub fn isolate_config() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("config");
+    std::env::set_var("HUD_MANAGER_CONFIG_DIR", dir);
+}
+
+// set up the way the app does before its first scan
+pub fn huds(custom_dir: &Path) -> Huds {
+    isolate_config();
+    let mut huds = Huds::default();
+    huds.profile = Some(Profile {
+        name: "test".to_string(),
+        custom_dir: custom_dir.to_path_buf(),
+    });
+
+    huds.update_favorites().unwrap();
+    huds
+}
+
+pub fn scan(custom_dir: &Path) -> Huds {
+    let mut huds = huds(custom_dir);
+    huds.scan_for_huds().unwrap();
+    huds
+}
+
+pub fn folders(dir: &Path) -> Vec<String> {
+    let mut folders = fs::read_dir(dir)
+        .unwrap()
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    folders.sort_unstable();
+
+    folders
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use hud_manager::find_custom_dir;
+
+fn find(start: &str) -> Option<PathBuf> {
+    find_custom_dir(Path::new(start))
+}
+
+#[test]
+fn finds_custom_from_the_huds_folder() {
+    assert_eq!(find("/tf/custom/huds"), Some(PathBuf::from("/tf/custom")));
+}
+
+#[test]
+fn finds_custom_from_any_depth() {
+    assert_eq!(
+        find("/games/tf/custom/huds/tools/hud_manager"),
+        Some(PathBuf::from("/games/tf/custom"))
+    );
+}
+
+#[test]
+fn finds_custom_in_a_linux_steam_library() {
+    let start = "/home/user/.local/share/Steam/steamapps/common/Team Fortress 2/tf/custom/huds";
+    assert_eq!(
+        find(start),
+        Some(PathBuf::from(
+            "/home/user/.local/share/Steam/steamapps/common/Team Fortress 2/tf/custom"
+        ))
+    );
+}
+
+#[test]
+fn prefers_tf_custom_over_a_nested_custom() {
+    assert_eq!(
+        find("/tf/custom/huds/somehud/custom"),
+        Some(PathBuf::from("/tf/custom"))
+    );
+}
+
+#[test]
+fn only_whole_folder_names_count() {
+    assert_eq!(find("/tf/my_custom/huds"), None);
+    assert_eq!(find("/tf/customs"), None);
+}
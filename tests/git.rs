@@ -0,0 +1,108 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use hud_manager::{ActivationStrategy, HudError, PullOutcome};
+
+use common::{hud_dirs, scan};
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+// a bare `remote` with one commit, and a checkout of it as the hud `a`
+fn custom_dir_with_git_hud() -> (tempfile::TempDir, tempfile::TempDir) {
+    let remote = tempfile::tempdir().unwrap();
+    git(remote.path(), &["init", "--bare", "--quiet", "-b", "main"]);
+    push_commit(remote.path(), "1");
+
+    let custom_dir = tempfile::tempdir().unwrap();
+    let huds_dir = custom_dir.path().join("huds");
+    fs::create_dir_all(&huds_dir).unwrap();
+    git(
+        &huds_dir,
+        &["clone", "--quiet", remote.path().to_str().unwrap(), "a"],
+    );
+    hud_dirs(&huds_dir, &["b"]);
+
+    (custom_dir, remote)
+}
+
+fn push_commit(remote: &Path, version: &str) {
+    let work = tempfile::tempdir().unwrap();
+    git(
+        work.path(),
+        &["clone", "--quiet", remote.to_str().unwrap(), "."],
+    );
+    fs::write(
+        work.path().join("info.vdf"),
+        format!("\"a\" {{ \"version\" \"{version}\" }}"),
+    )
+    .unwrap();
+    git(work.path(), &["add", "."]);
+    git(work.path(), &["commit", "--quiet", "-m", version]);
+    git(work.path(), &["push", "--quiet", "origin", "HEAD:main"]);
+}
+
+#[test]
+fn updates_report_whether_anything_changed() {
+    let (custom_dir, remote) = custom_dir_with_git_hud();
+    let huds = scan(custom_dir.path());
+
+    let git_huds = huds.huds.iter().filter(|h| h.is_git_repo);
+    assert_eq!(git_huds.map(|h| h.name.as_str()).collect::<Vec<_>>(), ["a"]);
+    assert_eq!(huds.update_hud("a", false).unwrap(), PullOutcome::UpToDate);
+
+    push_commit(remote.path(), "2");
+    assert!(matches!(
+        huds.update_hud("a", false).unwrap(),
+        PullOutcome::FastForwarded { .. }
+    ));
+    let info = fs::read_to_string(custom_dir.path().join("huds/a/info.vdf")).unwrap();
+    assert!(info.contains("\"2\""));
+}
+
+#[test]
+fn local_changes_stop_the_update() {
+    let (custom_dir, remote) = custom_dir_with_git_hud();
+    let huds = scan(custom_dir.path());
+    fs::write(custom_dir.path().join("huds/a/info.vdf"), "edited").unwrap();
+    push_commit(remote.path(), "2");
+
+    assert!(matches!(
+        huds.update_hud("a", false),
+        Err(HudError::GitLocalChanges(hud)) if hud == "a"
+    ));
+    let info = fs::read_to_string(custom_dir.path().join("huds/a/info.vdf")).unwrap();
+    assert_eq!(info, "edited");
+}
+
+#[test]
+fn updating_the_active_hud_has_to_be_asked_for() {
+    let (custom_dir, remote) = custom_dir_with_git_hud();
+    let mut huds = scan(custom_dir.path());
+    huds.activation_strategy = ActivationStrategy::Copy;
+    huds.set_active_hud("a").unwrap();
+    push_commit(remote.path(), "2");
+
+    assert!(matches!(
+        huds.update_hud("a", false),
+        Err(HudError::HudAlreadyActive { .. })
+    ));
+
+    huds.update_hud("a", true).unwrap();
+    // both the copy in `custom` and the one it's copied from next time
+    for hud in ["a", "huds/a"] {
+        let info = fs::read_to_string(custom_dir.path().join(hud).join("info.vdf")).unwrap();
+        assert!(info.contains("\"2\""), "{hud} wasn't updated");
+    }
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use hud_manager::{ExistingHud, HudError, Huds};
+
+use common::{custom_dir, scan};
+
+fn write_zip(path: &Path, files: &[(&str, &str)]) {
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+
+    for (name, contents) in files {
+        zip.start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+
+    zip.finish().unwrap();
+}
+
+fn install(huds: &mut Huds, archive: &Path, existing: ExistingHud) -> Result<String, HudError> {
+    huds.install_from_zip(archive, existing, |_, _| {})
+}
+
+#[test]
+fn installs_from_a_github_style_wrapper_folder() {
+    let custom_dir = custom_dir(&[]);
+    let archive = custom_dir.path().join("download.zip");
+    write_zip(
+        &archive,
+        &[
+            ("flawhud-main/info.vdf", "\"flawhud\" {}"),
+            ("flawhud-main/resource/ui/hudlayout.res", ""),
+        ],
+    );
+    let mut huds = scan(custom_dir.path());
+
+    let name = install(&mut huds, &archive, ExistingHud::Refuse).unwrap();
+
+    assert_eq!(name, "flawhud");
+    let hud = custom_dir.path().join("huds").join("flawhud");
+    assert!(hud.join("info.vdf").is_file());
+    assert!(hud.join("resource/ui/hudlayout.res").is_file());
+    assert!(huds.huds.iter().any(|h| h.name == "flawhud"));
+}
+
+#[test]
+fn installs_from_the_zip_root_under_the_archive_name() {
+    let custom_dir = custom_dir(&[]);
+    let archive = custom_dir.path().join("somehud.zip");
+    write_zip(&archive, &[("info.vdf", ""), ("scripts/hudlayout.res", "")]);
+    let mut huds = scan(custom_dir.path());
+
+    let name = install(&mut huds, &archive, ExistingHud::Refuse).unwrap();
+
+    assert_eq!(name, "somehud");
+    assert!(custom_dir.path().join("huds/somehud/info.vdf").is_file());
+}
+
+#[test]
+fn takes_the_shallowest_info_vdf_over_a_nested_one() {
+    let custom_dir = custom_dir(&[]);
+    let archive = custom_dir.path().join("download.zip");
+    write_zip(
+        &archive,
+        &[
+            ("hud/aaa/samples/other/info.vdf", "\"other\" {}"),
+            ("hud/info.vdf", "\"hud\" {}"),
+            ("hud/resource/ui/hudlayout.res", ""),
+        ],
+    );
+    let mut huds = scan(custom_dir.path());
+
+    let name = install(&mut huds, &archive, ExistingHud::Refuse).unwrap();
+
+    assert_eq!(name, "hud");
+    let hud = custom_dir.path().join("huds").join("hud");
+    assert!(hud.join("resource/ui/hudlayout.res").is_file());
+    assert!(hud.join("aaa/samples/other/info.vdf").is_file());
+}
+
+#[test]
+fn refuses_to_overwrite_unless_forced() {
+    let custom_dir = custom_dir(&[]);
+    let archive = custom_dir.path().join("hud.zip");
+    write_zip(&archive, &[("hud/info.vdf", "new")]);
+    let existing = custom_dir.path().join("huds/hud");
+    fs::create_dir_all(&existing).unwrap();
+    fs::write(existing.join("info.vdf"), "old").unwrap();
+    let mut huds = scan(custom_dir.path());
+
+    let e = install(&mut huds, &archive, ExistingHud::Refuse).unwrap_err();
+    assert!(matches!(e, HudError::AlreadyInstalled(hud) if hud == "hud"));
+    assert_eq!(
+        fs::read_to_string(existing.join("info.vdf")).unwrap(),
+        "old"
+    );
+    assert!(!custom_dir.path().join("huds/.installing").exists());
+
+    install(&mut huds, &archive, ExistingHud::Overwrite).unwrap();
+    assert_eq!(
+        fs::read_to_string(existing.join("info.vdf")).unwrap(),
+        "new"
+    );
+
+    let name = install(&mut huds, &archive, ExistingHud::KeepBoth).unwrap();
+    assert_eq!(name, "hud-2");
+}
+
+#[test]
+fn a_disabled_hud_counts_as_installed() {
+    let custom_dir = custom_dir(&[]);
+    let archive = custom_dir.path().join("hud.zip");
+    write_zip(&archive, &[("hud/info.vdf", "new")]);
+    let disabled = custom_dir.path().join("huds/.disabled/hud");
+    fs::create_dir_all(&disabled).unwrap();
+    fs::write(disabled.join("info.vdf"), "old").unwrap();
+    let mut huds = scan(custom_dir.path());
+
+    let e = install(&mut huds, &archive, ExistingHud::Refuse).unwrap_err();
+    assert!(matches!(e, HudError::AlreadyInstalled(hud) if hud == "hud"));
+    assert!(!custom_dir.path().join("huds/hud").exists());
+
+    let name = install(&mut huds, &archive, ExistingHud::KeepBoth).unwrap();
+    assert_eq!(name, "hud-2");
+
+    install(&mut huds, &archive, ExistingHud::Overwrite).unwrap();
+    assert_eq!(
+        fs::read_to_string(disabled.join("info.vdf")).unwrap(),
+        "new"
+    );
+    assert!(!custom_dir.path().join("huds/hud").exists());
+}
+
+#[test]
+fn rejects_zips_without_a_hud() {
+    let custom_dir = custom_dir(&[]);
+    let archive = custom_dir.path().join("notahud.zip");
+    write_zip(&archive, &[("readme.txt", "")]);
+    let mut huds = scan(custom_dir.path());
+
+    assert!(install(&mut huds, &archive, ExistingHud::Refuse).is_err());
+    assert!(!custom_dir.path().join("huds/notahud").exists());
+}
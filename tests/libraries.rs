@@ -0,0 +1,101 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use hud_manager::{ActivationStrategy, Huds};
+
+use common::{custom_dir, folders, hud_dirs};
+
+fn scan(custom_dir: &Path, library: &Path) -> Huds {
+    let mut huds = common::huds(custom_dir);
+    huds.libraries = vec![library.to_path_buf()];
+    huds.scan_for_huds().unwrap();
+    huds
+}
+
+#[test]
+fn huds_from_a_library_are_copied_in_and_removed_on_switching_away() {
+    let custom_dir = custom_dir(&["a"]);
+    let library = tempfile::tempdir().unwrap();
+    hud_dirs(library.path(), &["ext"]);
+
+    let mut huds = scan(custom_dir.path(), library.path());
+    let ext = huds.huds.iter().find(|h| h.name == "ext").unwrap();
+    assert_eq!(ext.source.as_deref(), Some(library.path()));
+    let a = huds.huds.iter().find(|h| h.name == "a").unwrap();
+    assert_eq!(a.source, None);
+
+    // moving is the default, but the library keeps its copy
+    huds.set_active_hud("ext").unwrap();
+    assert_eq!(folders(custom_dir.path()), ["ext", "huds"]);
+    assert_eq!(folders(library.path()), ["ext"]);
+
+    // listed once, and still known to be from the library after a restart
+    let mut huds = scan(custom_dir.path(), library.path());
+    assert_eq!(huds.huds.iter().filter(|h| h.name == "ext").count(), 1);
+    assert_eq!(
+        huds.active_hud.as_ref().unwrap().source.as_deref(),
+        Some(library.path())
+    );
+
+    huds.set_active_hud("a").unwrap();
+    assert_eq!(folders(custom_dir.path()), ["a", "huds"]);
+    assert_eq!(
+        folders(&custom_dir.path().join("huds")),
+        Vec::<String>::new()
+    );
+    assert_eq!(folders(library.path()), ["ext"]);
+    let ext = huds.huds.iter().find(|h| h.name == "ext").unwrap();
+    assert_eq!(ext.path, library.path().join("ext"));
+}
+
+#[test]
+fn library_huds_cant_be_disabled_deleted_or_renamed() {
+    let custom_dir = custom_dir(&["a"]);
+    let library = tempfile::tempdir().unwrap();
+    hud_dirs(library.path(), &["ext"]);
+    let mut huds = scan(custom_dir.path(), library.path());
+
+    assert!(huds.disable_hud("ext").is_err());
+    assert!(huds.delete_hud("ext").is_err());
+    assert!(huds.rename_hud("ext", "other").is_err());
+    assert_eq!(folders(library.path()), ["ext"]);
+    assert!(library.path().join("ext").join("info.vdf").is_file());
+
+    huds.set_active_hud("ext").unwrap();
+    assert!(huds.rename_hud("ext", "other").is_err());
+    assert_eq!(folders(custom_dir.path()), ["ext", "huds"]);
+    assert_eq!(folders(library.path()), ["ext"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_failed_switch_puts_a_library_hud_back_from_its_library() {
+    let custom_dir = custom_dir(&["a"]);
+    let library = tempfile::tempdir().unwrap();
+    hud_dirs(library.path(), &["ext"]);
+    let mut huds = scan(custom_dir.path(), library.path());
+    huds.activation_strategy = ActivationStrategy::Copy;
+
+    huds.set_active_hud("ext").unwrap();
+    // copying a link to nowhere fails partway through
+    std::os::unix::fs::symlink("missing", custom_dir.path().join("huds/a/broken")).unwrap();
+
+    let e = huds.set_active_hud("a").unwrap_err();
+    assert!(
+        format!("{e:#}").contains("`ext` is still the active hud"),
+        "{e:#}"
+    );
+    assert_eq!(folders(custom_dir.path()), ["ext", "huds"]);
+    assert!(custom_dir.path().join("ext").join("info.vdf").is_file());
+    assert_eq!(folders(library.path()), ["ext"]);
+
+    // still known to be from the library, so switching away later removes only the copy
+    let sources = fs::read_to_string(custom_dir.path().join("huds/sources.json")).unwrap();
+    assert!(sources.contains("\"ext\""), "{sources}");
+    huds.deactivate().unwrap();
+    assert_eq!(folders(custom_dir.path()), ["huds"]);
+    assert!(!custom_dir.path().join("huds").join("ext").exists());
+    assert_eq!(folders(library.path()), ["ext"]);
+}
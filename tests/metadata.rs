@@ -0,0 +1,147 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use hud_manager::{Hud, ValidationWarning};
+
+use common::scan;
+
+fn scan_one(info_vdf: &str) -> Hud {
+    let custom_dir = tempfile::tempdir().unwrap();
+    let hud = custom_dir.path().join("huds").join("folder-name");
+    fs::create_dir_all(&hud).unwrap();
+    fs::write(hud.join("info.vdf"), info_vdf).unwrap();
+
+    scan(custom_dir.path()).huds.remove(0)
+}
+
+#[test]
+fn reads_name_version_and_author() {
+    let hud = scan_one(
+        r#"
+        // made by hand
+        "Some Hud"
+        {
+            "ui_version" "3" // bumped for the new menus
+            "author"     "someone"
+            "extra"
+            {
+                "nested" { "deeper" "value" }
+            }
+        }
+        "#,
+    );
+
+    assert_eq!(hud.name, "folder-name");
+    assert_eq!(hud.display_name.as_deref(), Some("Some Hud"));
+    assert_eq!(hud.ui_version, Some(3));
+    assert_eq!(hud.author.as_deref(), Some("someone"));
+    assert_eq!(hud.title(), "Some Hud");
+}
+
+#[test]
+fn empty_info_vdf_falls_back_to_the_folder_name() {
+    let hud = scan_one("");
+
+    assert_eq!(hud.display_name, None);
+    assert_eq!(hud.ui_version, None);
+    assert_eq!(hud.author, None);
+    assert_eq!(hud.title(), "folder-name");
+}
+
+#[test]
+fn unparseable_version_is_ignored() {
+    let hud = scan_one(r#""hud" { "ui_version" "three" }"#);
+
+    assert_eq!(hud.display_name.as_deref(), Some("hud"));
+    assert_eq!(hud.ui_version, None);
+}
+
+#[test]
+fn thumbnail_is_found_relative_to_the_hud_and_loaded_on_demand() {
+    let custom_dir = tempfile::tempdir().unwrap();
+    let hud = custom_dir.path().join("huds").join("folder-name");
+    fs::create_dir_all(hud.join("materials")).unwrap();
+    fs::File::create(hud.join("info.vdf")).unwrap();
+    image::RgbaImage::new(400, 200)
+        .save(hud.join("materials").join("Preview.PNG"))
+        .unwrap();
+
+    let mut huds = scan(custom_dir.path());
+    let thumbnail = Path::new("materials").join("Preview.PNG");
+    assert_eq!(huds.huds[0].thumbnail.as_deref(), Some(thumbnail.as_path()));
+
+    huds.set_active_hud("folder-name").unwrap();
+    let active = huds.active_hud.as_ref().unwrap();
+    assert_eq!(
+        active.thumbnail_path(),
+        Some(custom_dir.path().join("folder-name").join(&thumbnail))
+    );
+
+    let image = active.load_thumbnail(100).unwrap();
+    assert_eq!(image.dimensions(), (100, 50));
+}
+
+#[test]
+fn no_image_means_no_thumbnail() {
+    let hud = scan_one("");
+
+    assert_eq!(hud.thumbnail, None);
+    assert!(hud.load_thumbnail(100).is_none());
+}
+
+#[test]
+fn huds_like_the_generated_test_ones_are_flagged_as_incomplete() {
+    // `cargo xtask gen-test-huds` makes an empty `info.vdf` and nothing else
+    let custom_dir = tempfile::tempdir().unwrap();
+    let hud = custom_dir.path().join("huds").join("generated-hud");
+    fs::create_dir_all(&hud).unwrap();
+    fs::File::create(hud.join("info.vdf")).unwrap();
+    let hud = scan(custom_dir.path()).huds.remove(0);
+
+    assert_eq!(
+        hud.validate(),
+        [
+            ValidationWarning::EmptyInfoVdf,
+            ValidationWarning::MissingFolder("resource"),
+            ValidationWarning::MissingFolder("scripts"),
+        ]
+    );
+    assert_eq!(hud.validate()[2].to_string(), "missing `scripts` folder");
+
+    fs::create_dir(hud.path.join("resource")).unwrap();
+    fs::create_dir(hud.path.join("scripts")).unwrap();
+    fs::write(hud.path.join("info.vdf"), r#""hud" {}"#).unwrap();
+    assert!(hud.validate().is_empty());
+}
+
+#[test]
+fn parallel_scan_matches_a_single_threaded_one() {
+    let custom_dir = tempfile::tempdir().unwrap();
+    for i in 0..200 {
+        let hud = custom_dir.path().join("huds").join(format!("hud-{i:03}"));
+        fs::create_dir_all(&hud).unwrap();
+        fs::write(
+            hud.join("info.vdf"),
+            format!(
+                "\"Hud {i}\"\n{{\n\t\"ui_version\"\t\"{}\"\n\t\"author\"\t\"author {}\"\n}}\n",
+                i % 4,
+                i % 7
+            ),
+        )
+        .unwrap();
+    }
+
+    let parallel = scan(custom_dir.path());
+    let single_threaded = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap()
+        .install(|| scan(custom_dir.path()));
+
+    assert_eq!(parallel.huds.len(), 200);
+    assert!(parallel.huds == single_threaded.huds);
+    assert_eq!(parallel.huds[5].display_name.as_deref(), Some("Hud 5"));
+    assert_eq!(parallel.huds[5].author.as_deref(), Some("author 5"));
+}
@@ -0,0 +1,24 @@
+use hud_manager::rows_in_view;
+
+#[test]
+fn builds_only_the_rows_in_view_and_keeps_their_positions() {
+    let rows = 0..10;
+    let even = |n: &i32| n % 2 == 0;
+
+    let built: Vec<_> = rows_in_view(rows.clone(), even, 1..3).collect();
+    assert_eq!(built, [(2, 2), (4, 4)]);
+
+    // a range running past the end only builds what there is
+    let built: Vec<_> = rows_in_view(rows.clone(), even, 3..8).collect();
+    assert_eq!(built, [(6, 6), (8, 8)]);
+
+    assert_eq!(rows_in_view(rows, even, 5..8).count(), 0);
+}
+
+#[test]
+fn positions_count_rows_the_list_leaves_out() {
+    let rows = ["fav", "pinned", "fav", "hud", "hud"];
+
+    let built: Vec<_> = rows_in_view(rows.into_iter(), |r| *r == "hud", 0..2).collect();
+    assert_eq!(built, [(3, "hud"), (4, "hud")]);
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use hud_manager::{Hud, Huds};
+
+fn scan(huds: &[&str]) -> (tempfile::TempDir, Huds) {
+    let custom_dir = common::custom_dir(huds);
+    let scanned = common::scan(custom_dir.path());
+
+    (custom_dir, scanned)
+}
+
+fn names<'a>(huds: impl IntoIterator<Item = &'a Hud>) -> Vec<&'a str> {
+    huds.into_iter().map(|h| h.name.as_str()).collect()
+}
+
+#[test]
+fn a_substring_ranks_above_scattered_letters() {
+    let (_custom_dir, huds) = scan(&["f-l-a-w", "flawhud", "toonhud"]);
+
+    assert_eq!(names(huds.search("flaw", 0.0)), ["flawhud", "f-l-a-w"]);
+}
+
+#[test]
+fn the_threshold_drops_weak_matches() {
+    let (_custom_dir, huds) = scan(&["f-l-a-w", "flawhud", "toonhud"]);
+
+    assert_eq!(names(huds.search("flaw", 1.0)), ["flawhud"]);
+}
+
+#[test]
+fn an_empty_query_returns_every_hud() {
+    let (_custom_dir, huds) = scan(&["flawhud", "toonhud"]);
+
+    assert_eq!(huds.search("  ", 1.0).len(), 2);
+}
@@ -2,34 +2,114 @@
 
 type DynError = Box<dyn std::error::Error>;
 
+const TEST_HUDS: usize = 50;
+
 fn main() -> Result<(), DynError> {
     match std::env::args().nth(1).as_deref() {
-        Some("gen-test-huds") => gen_test_huds()?,
+        Some("gen-test-huds") => {
+            let args = std::env::args().skip(2).collect::<Vec<_>>();
+            gen_test_huds(&args)?
+        }
         _ => print_help(),
     }
 
     Ok(())
 }
 
-fn gen_test_huds() -> Result<(), DynError> {
-    let huds_dir = std::path::Path::new(&env!("CARGO_MANIFEST_DIR"))
+#[derive(Default)]
+struct GenOptions {
+    with_metadata: bool,
+    with_resource: bool,
+    favorites: usize,
+    broken: usize,
+}
+
+impl GenOptions {
+    fn parse(args: &[String]) -> Result<Self, DynError> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut count = || -> Result<usize, DynError> {
+                let count = args.next().ok_or(format!("`{arg}` needs a number"))?;
+                count
+                    .parse()
+                    .map_err(|_| format!("`{count}` isn't a number").into())
+            };
+
+            match arg.as_str() {
+                "--with-metadata" => options.with_metadata = true,
+                "--with-resource" => options.with_resource = true,
+                "--favorites" => options.favorites = count()?,
+                "--broken" => options.broken = count()?,
+                _ => return Err(format!("unknown option `{arg}`").into()),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn gen_test_huds(args: &[String]) -> Result<(), DynError> {
+    let options = GenOptions::parse(args)?;
+
+    let custom_dir = std::path::Path::new(&env!("CARGO_MANIFEST_DIR"))
         .ancestors()
         .nth(1)
         .unwrap()
         .to_path_buf()
-        .join("target/debug/custom/huds");
+        .join("target/debug/custom");
+    let huds_dir = custom_dir.join("huds");
 
     if !huds_dir.exists() {
         fs::create_dir_all(&huds_dir)?;
     }
 
     let mut name_gen = names::Generator::default();
+    let mut names = Vec::new();
 
-    for _ in 0..50 {
-        let hud = huds_dir.join(name_gen.next().unwrap() + "-hud");
+    for _ in 0..TEST_HUDS {
+        let name = name_gen.next().unwrap() + "-hud";
+        let hud = huds_dir.join(&name);
 
         fs::create_dir_all(&hud)?;
-        fs::File::create(hud.join("info.vdf"))?;
+        if options.with_metadata {
+            let author = name_gen.next().unwrap();
+            fs::write(
+                hud.join("info.vdf"),
+                format!(
+                    "\"{name}\"\n{{\n\t\"ui_version\"\t\"3\"\n\t\"author\"\t\"{author}\"\n}}\n"
+                ),
+            )?;
+        } else {
+            fs::File::create(hud.join("info.vdf"))?;
+        }
+        if options.with_resource {
+            fs::create_dir_all(hud.join("resource"))?;
+            fs::create_dir_all(hud.join("scripts"))?;
+        }
+
+        names.push(name);
+    }
+
+    if options.favorites > 0 {
+        // the generated names don't need escaping
+        let favorites = names
+            .iter()
+            .take(options.favorites)
+            .map(|name| format!("{{\"name\":\"{name}\"}}"))
+            .collect::<Vec<_>>();
+        fs::write(
+            huds_dir.join("favorites.json"),
+            format!("{{\"version\":1,\"favorites\":[{}]}}", favorites.join(",")),
+        )?;
+    }
+
+    // these go straight in `custom`, which is where a hud without an `info.vdf` causes trouble
+    for _ in 0..options.broken {
+        let hud = custom_dir.join(name_gen.next().unwrap() + "-broken-hud");
+
+        fs::create_dir_all(hud.join("resource"))?;
     }
 
     Ok(())
@@ -40,7 +120,13 @@ fn print_help() {
         "Tasks:
 
 gen-test-huds        generates a `custom` folder and some hud folders for testing
-                     in `target/debug`
+                     in `target/debug`, point `HUD_MANAGER_CUSTOM_DIR` at it to
+                     use it from anywhere
+
+    --with-metadata  fill in each `info.vdf` with a name, ui_version and author
+    --with-resource  add `resource` and `scripts` folders so the huds look complete
+    --favorites <n>  mark the first n huds as favorites
+    --broken <n>     add n folders without an `info.vdf` to `custom`
 "
     )
 }